@@ -1,6 +1,7 @@
 use crate::app::modules::core::add::Add;
 use crate::app::modules::io::use_io;
-use crate::app::modules::manifest::models::Provider;
+use crate::app::modules::manifest::models::{Channel, ModEntry, Provider};
+use crate::app::McpmError;
 use clap::Args;
 
 #[derive(Args)]
@@ -16,23 +17,25 @@ pub struct AddCommand {
 
     #[arg(long)]
     pub search: bool,
+
+    /// Least stable release channel to pick from, saved with the mod (default: the manifest's)
+    #[arg(long)]
+    pub channel: Option<Channel>,
 }
 
 impl AddCommand {
-    pub async fn handle(&self) {
-        let io = use_io();
-
-        match Add::run(
+    pub async fn handle(&self) -> Result<ModEntry, McpmError> {
+        let entry = Add::run(
             &self.id,
             self.version.clone(),
             self.provider.clone(),
             self.exact,
             self.search,
+            self.channel,
         )
-        .await
-        {
-            Ok(_) => io.success("Mod added successfully"),
-            Err(e) => io.error(&e, None),
-        }
+        .await?;
+
+        use_io().success("Mod added successfully");
+        Ok(entry)
     }
 }
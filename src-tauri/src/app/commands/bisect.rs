@@ -0,0 +1,69 @@
+use crate::app::{
+    commands::output::is_text,
+    modules::{
+        core::bisect::{Bisect, BisectOutcome, BisectState},
+        io::{use_io, IOInstance},
+    },
+    McpmError,
+};
+use clap::{Args, Subcommand};
+
+#[derive(Args)]
+pub struct BisectCommand {
+    #[command(subcommand)]
+    pub action: BisectAction,
+}
+
+#[derive(Subcommand)]
+pub enum BisectAction {
+    /// Start bisecting by disabling half of the non-library mods
+    Start,
+    /// The game works with the current set of mods
+    Good,
+    /// The game still crashes with the current set of mods
+    Bad,
+    /// Stop bisecting and restore the original mods
+    Reset,
+}
+
+impl BisectCommand {
+    pub async fn handle(&self) -> Result<BisectOutcome, McpmError> {
+        let io = use_io();
+
+        let outcome = match self.action {
+            BisectAction::Start => BisectOutcome::Next(Bisect::start().await?),
+            BisectAction::Good => Bisect::mark(true).await?,
+            BisectAction::Bad => Bisect::mark(false).await?,
+            BisectAction::Reset => {
+                Bisect::reset().await?;
+                BisectOutcome::Reset
+            }
+        };
+
+        match &outcome {
+            BisectOutcome::Next(state) => Self::print_step(&io, state),
+            BisectOutcome::Found { culprit } => {
+                io.success(&format!("Found the culprit: {}", culprit));
+                io.info("Original mods restored");
+            }
+            BisectOutcome::Reset => io.success("Bisect session ended, original mods restored"),
+        }
+        Ok(outcome)
+    }
+
+    fn print_step(io: &IOInstance, state: &BisectState) {
+        io.info(&format!(
+            "Step {}: disabled {} of {} suspects (about {} step(s) left)",
+            state.step,
+            state.testing.len(),
+            state.suspects.len(),
+            state.steps_left()
+        ));
+        if is_text() {
+            for key in &state.testing {
+                println!("  {}", key);
+            }
+        }
+        io.info("Does the game work now? Run `mcpm bisect good` if it does, `mcpm bisect bad` if it still crashes");
+    }
+}
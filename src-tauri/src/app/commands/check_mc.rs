@@ -0,0 +1,98 @@
+use clap::Args;
+
+use crate::app::{
+    commands::output::is_text,
+    helpers::as_str,
+    modules::{
+        core::check_mc::{CheckMc, CheckMcReport},
+        io::use_io,
+        manifest::models::ModLoader,
+    },
+    McpmError,
+};
+
+#[derive(Args)]
+pub struct CheckMcCommand {
+    /// Minecraft version to check, e.g. 1.21.8
+    pub version: String,
+
+    /// Accept a snapshot or other pre-release version
+    #[arg(long)]
+    pub snapshot: bool,
+
+    /// Loader to check (default: the manifest's)
+    #[arg(long, value_enum)]
+    pub loader: Option<ModLoader>,
+}
+
+impl CheckMcCommand {
+    pub async fn handle(&self) -> Result<CheckMcReport, McpmError> {
+        let io = use_io();
+        let report = CheckMc::run(&self.version, self.loader, self.snapshot).await?;
+        let target = format!(
+            "Minecraft {} + {}",
+            report.minecraft_version,
+            as_str(&report.modloader)
+        );
+
+        if report.mods.is_empty() {
+            io.info("No mods in the manifest yet");
+            return Ok(report);
+        }
+        if is_text() {
+            Self::print_table(&report);
+        }
+
+        let incompatible = report.incompatible();
+        if incompatible.is_empty() {
+            io.success(&format!(
+                "All {} mod(s) are available for {}",
+                report.mods.len(),
+                target
+            ));
+            io.info(&format!(
+                "Run `mcpm migrate --mc {}` to switch",
+                report.minecraft_version
+            ));
+        } else {
+            io.warn(&format!(
+                "{} of {} mod(s) have no compatible version for {}: {}",
+                incompatible.len(),
+                report.mods.len(),
+                target,
+                incompatible.join(", ")
+            ));
+        }
+        Ok(report)
+    }
+
+    fn print_table(report: &CheckMcReport) {
+        println!(
+            "| {:<30} | {:<20} | {:<20} | {:<20} |",
+            "Mod", "Current", "Compatible", "Latest"
+        );
+        println!("{}", "-".repeat(103));
+
+        let mut details = vec![];
+        for m in &report.mods {
+            println!(
+                "| {:<30} | {:<20} | {:<20} | {:<20} |",
+                m.key,
+                m.current.as_deref().unwrap_or("-"),
+                m.compatible.as_deref().unwrap_or("-"),
+                m.latest.as_deref().unwrap_or("-"),
+            );
+            if let Some(detail) = &m.detail {
+                details.push((&m.key, detail));
+            }
+        }
+
+        if !details.is_empty() {
+            println!("\nNot available:");
+            for (key, detail) in details {
+                println!("  {}: {}", key, detail);
+            }
+        }
+        println!();
+    }
+}
@@ -0,0 +1,80 @@
+use crate::app::{
+    commands::output::is_text,
+    helpers::as_str,
+    modules::{
+        core::config::{ConfigEntry, Configure},
+        io::use_io,
+    },
+    McpmError,
+};
+use clap::{Args, Subcommand};
+use serde_json::Value;
+
+#[derive(Args)]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigAction {
+    /// Print the effective value of a key, e.g. `concurrency` or `api_keys.modrinth`
+    Get { key: String },
+    /// Write a value to the project config (.mcpmrc), or the user's config with --global
+    Set {
+        key: String,
+        value: String,
+        /// Write to ~/.config/mcpm/config.toml instead
+        #[arg(long)]
+        global: bool,
+    },
+    /// Print every effective value and where it comes from
+    List,
+}
+
+impl ConfigCommand {
+    pub fn handle(&self) -> Result<Value, McpmError> {
+        let io = use_io();
+
+        let document = match &self.action {
+            ConfigAction::Get { key } => {
+                let entry = Configure::get(key)?;
+                if is_text() {
+                    println!("{}", entry.value);
+                }
+                serde_json::to_value(entry)
+            }
+            ConfigAction::Set { key, value, global } => {
+                let change = Configure::set(key, value, *global)?;
+                io.success(&format!(
+                    "Set {} = {} in {}",
+                    change.key,
+                    change.value,
+                    change.path.display()
+                ));
+                serde_json::to_value(change)
+            }
+            ConfigAction::List => {
+                let entries = Configure::list();
+                if is_text() {
+                    Self::print_entries(&entries);
+                }
+                serde_json::to_value(entries)
+            }
+        };
+        Ok(document.expect("result documents serialize"))
+    }
+
+    fn print_entries(entries: &[ConfigEntry]) {
+        let width = entries.iter().map(|e| e.key.len()).max().unwrap_or(0);
+        for e in entries {
+            println!(
+                "{:<width$} = {}  ({})",
+                e.key,
+                e.value,
+                as_str(&e.source),
+                width = width
+            );
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use crate::app::{
+    helpers::as_str,
+    modules::{
+        core::convert::{Convert, ConvertResult},
+        io::use_io,
+        manifest::ManifestFormat,
+    },
+    McpmError,
+};
+use clap::Args;
+
+#[derive(Args)]
+pub struct ConvertCommand {
+    /// Format to convert the manifest to
+    #[arg(long, value_enum)]
+    pub to: ManifestFormat,
+}
+
+impl ConvertCommand {
+    pub fn handle(&self) -> Result<ConvertResult, McpmError> {
+        let io = use_io();
+
+        let result = Convert::run(self.to)?;
+        if result.converted {
+            io.success(&format!(
+                "Converted {} to {}",
+                result.from.display(),
+                result.to.display()
+            ));
+            if let Some(config) = &result.config {
+                io.info(&format!("Moved the [config] table to {}", config.display()));
+            }
+        } else {
+            io.info(&format!(
+                "{} is already {}",
+                result.from.display(),
+                as_str(&self.to)
+            ));
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,49 @@
+use clap::Args;
+
+use crate::app::{
+    modules::{
+        core::doctor::{Doctor, DoctorReport},
+        io::use_io,
+    },
+    McpmError,
+};
+
+#[derive(Args)]
+pub struct DoctorCommand {
+    /// Update manifest and lock keys of renamed projects and record missing project ids
+    #[arg(long)]
+    pub fix_slugs: bool,
+}
+
+impl DoctorCommand {
+    pub async fn handle(&self) -> Result<DoctorReport, McpmError> {
+        let io = use_io();
+        let report = Doctor::run(self.fix_slugs).await?;
+
+        for r in &report.renamed {
+            io.warn(&format!("{} was renamed to {}", r.from, r.to));
+        }
+        for key in &report.missing {
+            io.warn(&format!("{} is not known to its provider", key));
+        }
+
+        if report.fixed {
+            io.success(&format!(
+                "Updated {} renamed key(s) and recorded {} project id(s). Run `mcpm install` to rename installed files",
+                report.renamed.len(),
+                report.missing_ids.len()
+            ));
+        } else if !report.renamed.is_empty() || !report.missing_ids.is_empty() {
+            if !report.missing_ids.is_empty() {
+                io.info(&format!(
+                    "No project id recorded for: {}",
+                    report.missing_ids.join(", ")
+                ));
+            }
+            io.info("Run `mcpm doctor --fix-slugs` to update the manifest");
+        } else if report.missing.is_empty() {
+            io.success("No problems found");
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::app::{
+    commands::output::is_text,
+    modules::{core::history::History, io::use_io, snapshot::models::Snapshot},
+    McpmError,
+};
+use clap::Args;
+
+#[derive(Args)]
+pub struct HistoryCommand {}
+
+impl HistoryCommand {
+    pub fn handle(&self) -> Result<Vec<Snapshot>, McpmError> {
+        let io = use_io();
+        let snapshots = History::run();
+
+        if snapshots.is_empty() {
+            io.info("No snapshots recorded yet");
+        }
+        if snapshots.is_empty() || !is_text() {
+            return Ok(snapshots);
+        }
+
+        println!(
+            "| {:<20} | {:<25} | {:<50} |",
+            "Snapshot", "Created", "Followed by"
+        );
+        println!("{}", "-".repeat(104));
+
+        for s in &snapshots {
+            println!("| {:<20} | {:<25} | {:<50} |", s.id, s.created_at, s.note);
+        }
+        Ok(snapshots)
+    }
+}
@@ -1,12 +1,20 @@
 use clap::Args;
 
-use crate::app::modules::core::init::Init;
+use crate::app::{modules::core::init::Init, McpmError};
 
 #[derive(Args)]
-pub struct InitCommand;
+pub struct InitCommand {
+    /// Minecraft version of the new pack (default: the latest release)
+    #[arg(long = "mc")]
+    pub minecraft_version: Option<String>,
+
+    /// Allow a snapshot, or default to the latest one
+    #[arg(long)]
+    pub snapshot: bool,
+}
 
 impl InitCommand {
-    pub async fn handle(&self) {
-        Init::run().await;
+    pub async fn handle(&self) -> Result<(), McpmError> {
+        Init::run(self.minecraft_version.clone(), self.snapshot).await
     }
 }
@@ -1,5 +1,13 @@
 // commands/install.rs
-use crate::app::modules::{core::install::Install, io::use_io};
+use crate::app::{
+    commands::output::is_text,
+    helpers::format_bytes,
+    modules::{
+        core::install::{Install, InstallPlan},
+        io::{use_io, IOInstance},
+    },
+    McpmError,
+};
 use clap::Args;
 
 #[derive(Args)]
@@ -9,15 +17,104 @@ pub struct InstallCommand {
 
     #[arg(long)]
     pub force_rehash: bool,
+
+    /// Print what would be installed without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Optional mods to install, by slug or `provider:slug`. Later installs keep them
+    /// Optional mods to install, by slug or `provider:slug`
+    #[arg(long, value_name = "MOD")]
+    pub with: Vec<String>,
 }
 
 impl InstallCommand {
-    pub async fn handle(&self) {
+    pub async fn handle(&self) -> Result<InstallPlan, McpmError> {
         let io = use_io();
 
-        match Install::run(self.no_cache, self.force_rehash).await {
-            Ok(_) => io.success("Installation completed successfully"),
-            Err(e) => io.error(&e, None),
+        if self.dry_run {
+            let plan = Install::plan(self.no_cache, self.force_rehash, &self.with).await?;
+            print_plan(&io, &plan);
+            return Ok(plan);
+        }
+
+        let plan = Install::run(self.no_cache, self.force_rehash, &self.with).await?;
+        io.success("Installation completed successfully");
+        print_left_out(&io, &plan);
+        Ok(plan)
+    }
+}
+
+pub fn print_plan(io: &IOInstance, plan: &InstallPlan) {
+    if !is_text() {
+        return;
+    }
+    print_left_out(io, plan);
+    if plan.is_empty() {
+        io.success("Nothing to do, everything is installed");
+        return;
+    }
+
+    if !plan.lock_changes.is_empty() {
+        io.info(&format!(
+            "Lock entries to change ({}):",
+            plan.lock_changes.len()
+        ));
+        for c in &plan.lock_changes {
+            println!(
+                "  {}: {} → {}",
+                c.key,
+                c.before.as_deref().unwrap_or("-"),
+                c.after.as_deref().unwrap_or("-")
+            );
+        }
+    }
+
+    if !plan.downloads.is_empty() {
+        io.info(&format!(
+            "Files to download ({}, {}):",
+            plan.downloads.len(),
+            format_bytes(plan.download_size())
+        ));
+        for d in &plan.downloads {
+            println!(
+                "  {} {} ({}) → {}",
+                d.key,
+                d.version,
+                d.size
+                    .map(format_bytes)
+                    .unwrap_or_else(|| "unknown size".into()),
+                d.path.display()
+            );
+        }
+    }
+
+    if !plan.copies.is_empty() {
+        io.info(&format!(
+            "Files to copy from cache ({}):",
+            plan.copies.len()
+        ));
+        for c in &plan.copies {
+            println!("  {} → {}", c.from.display(), c.to.display());
         }
     }
+
+    if !plan.deletions.is_empty() {
+        io.info(&format!(
+            "Files to delete from {} ({}):",
+            plan.mods_dir.display(),
+            plan.deletions.len()
+        ));
+        for p in &plan.deletions {
+            println!("  {}", p.display());
+        }
+    }
+}
+
+pub fn print_left_out(io: &IOInstance, plan: &InstallPlan) {
+    if is_text() && !plan.left_out.is_empty() {
+        io.info(&format!(
+            "Optional mods left out, add them with `--with`: {}",
+            plan.left_out.join(", ")
+        ));
+    }
 }
@@ -1,12 +1,49 @@
 use clap::Args;
 
-use crate::app::modules::core::list::List;
+use crate::app::{
+    commands::output::is_text,
+    modules::{
+        core::list::{List, ListEntry},
+        io::use_io,
+    },
+    McpmError,
+};
 
 #[derive(Args)]
 pub struct ListCommand {}
 
 impl ListCommand {
-    pub fn handle(&self) {
-        List::run();
+    pub async fn handle(&self) -> Result<Vec<ListEntry>, McpmError> {
+        let entries = List::run().await?;
+
+        if entries.is_empty() {
+            use_io().info("No mods in the manifest yet");
+        }
+        if entries.is_empty() || !is_text() {
+            return Ok(entries);
+        }
+
+        println!(
+            "| {:<30} | {:<15} | {:<20} | {:<10} |",
+            "Mod", "Spec", "Locked", "State"
+        );
+        println!("{}", "-".repeat(88));
+
+        for e in &entries {
+            let state = match (&e.pinned, e.disabled) {
+                (_, true) => "disabled".to_string(),
+                (Some(v), false) => format!("pinned {}", v),
+                (None, false) if e.optional => "optional".to_string(),
+                (None, false) => "".to_string(),
+            };
+            println!(
+                "| {:<30} | {:<15} | {:<20} | {:<10} |",
+                e.key,
+                e.spec.to_string(),
+                e.locked.as_deref().unwrap_or("-"),
+                state
+            );
+        }
+        Ok(entries)
     }
 }
@@ -0,0 +1,49 @@
+use clap::Args;
+
+use crate::app::{
+    helpers::as_str,
+    modules::{
+        core::migrate::{Migrate, MigrateResult},
+        io::use_io,
+        manifest::models::ModLoader,
+    },
+    McpmError,
+};
+
+#[derive(Args)]
+pub struct MigrateCommand {
+    /// Minecraft version to move the pack to
+    #[arg(long = "mc")]
+    pub minecraft_version: String,
+
+    /// Accept a snapshot or other pre-release version
+    #[arg(long)]
+    pub snapshot: bool,
+
+    /// Loader to move the pack to (default: the manifest's)
+    #[arg(long, value_enum)]
+    pub loader: Option<ModLoader>,
+}
+
+impl MigrateCommand {
+    pub async fn handle(&self) -> Result<MigrateResult, McpmError> {
+        let io = use_io();
+        let result = Migrate::run(&self.minecraft_version, self.loader, self.snapshot).await?;
+
+        if let Some(loader) = &result.loader {
+            io.info(&format!(
+                "Locked {} {}",
+                as_str(&loader.name),
+                loader.version
+            ));
+        }
+        io.success(&format!(
+            "Moved {} mod(s) from Minecraft {} to {} + {}. Run `mcpm install` to update the mods directory",
+            result.report.mods.len(),
+            result.from,
+            result.report.minecraft_version,
+            as_str(&result.report.modloader)
+        ));
+        Ok(result)
+    }
+}
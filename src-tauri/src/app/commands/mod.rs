@@ -1,13 +1,26 @@
 pub mod add;
+pub mod bisect;
+pub mod check_mc;
+pub mod config;
+pub mod convert;
+pub mod doctor;
+pub mod history;
 pub mod init;
 pub mod install;
 pub mod list;
+pub mod migrate;
 pub mod outdated;
+pub mod output;
+pub mod pin;
 pub mod remove;
+pub mod rollback;
 pub mod search;
+pub mod toggle;
 pub mod upgrade;
 
+use crate::app::modules::io::traits::LogFormat;
 use clap::{ArgGroup, Parser, Subcommand};
+use output::{report, to_document, OutputFormat};
 
 #[derive(Parser)]
 #[command(
@@ -29,11 +42,15 @@ pub struct Cli {
     #[arg(short, long, global = true)]
     pub quiet: bool,
 
+    /// Manifest file or project directory (default: nearest mcpm.json in this or a parent directory)
+    #[arg(long, global = true)]
+    pub manifest: Option<String>,
+
     /// Cache directory. (env: MCPM_CACHE_DIR, default: ~/.mcpm/cache)
     #[arg(long, global = true)]
     pub cache_dir: Option<String>,
 
-    /// Output directory. (env: MCPM_OUTPUT_DIR, default: ./)
+    /// Output directory, relative to the manifest. (env: MCPM_OUTPUT_DIR, default: the manifest's directory)
     #[arg(long, global = true)]
     pub output_dir: Option<String>,
 
@@ -41,6 +58,26 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub mods_dir: Option<String>,
 
+    /// Never use the network, only cached files. (env: MCPM_OFFLINE)
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Output format. `json` prints one result document per command
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub output: OutputFormat,
+
+    /// Log format. `json` writes one event per line to stderr
+    #[arg(long, global = true, value_enum, default_value_t)]
+    pub log_format: LogFormat,
+
+    /// Never prompt. Defaults are used and ambiguous choices fail (also when CI is set)
+    #[arg(long, global = true)]
+    pub non_interactive: bool,
+
+    /// Answer yes to every confirmation. Implies --non-interactive
+    #[arg(short, long, global = true)]
+    pub yes: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -62,19 +99,83 @@ pub enum Commands {
     Outdated(outdated::OutdatedCommand),
     List(list::ListCommand),
     Search(search::SearchCommand),
+    /// Keep a mod in the manifest and lock but install it as `.jar.disabled`
+    Disable(toggle::DisableCommand),
+    /// Re-enable a disabled mod
+    Enable(toggle::EnableCommand),
+    /// Freeze a mod at a version so upgrades leave it alone
+    Pin(pin::PinCommand),
+    /// Let a pinned mod follow its version spec again
+    Unpin(pin::UnpinCommand),
+    /// Find the mod causing a crash by disabling half of the mods at a time
+    Bisect(bisect::BisectCommand),
+    /// Restore the state before a previous install
+    Rollback(rollback::RollbackCommand),
+    /// List recorded snapshots
+    History(history::HistoryCommand),
+    /// Read and write settings in .mcpmrc and ~/.config/mcpm/config.toml
+    Config(config::ConfigCommand),
+    /// Rewrite the manifest as mcpm.toml or mcpm.json
+    Convert(convert::ConvertCommand),
+    /// Check manifest entries against their provider, e.g. for renamed slugs
+    Doctor(doctor::DoctorCommand),
+    /// Check whether every mod has a version for another Minecraft version
+    CheckMc(check_mc::CheckMcCommand),
+    /// Move the pack to another Minecraft version once every mod supports it
+    Migrate(migrate::MigrateCommand),
 }
 
 impl Commands {
-    pub async fn run(self) {
+    /// Run the command, print its outcome and return the process exit code
+    pub async fn run(self) -> i32 {
+        let name = self.name();
+        let result = match self {
+            Commands::Init(cmd) => to_document(cmd.handle().await),
+            Commands::Install(cmd) => to_document(cmd.handle().await),
+            Commands::Upgrade(cmd) => to_document(cmd.handle().await),
+            Commands::Outdated(cmd) => to_document(cmd.handle().await),
+            Commands::List(cmd) => to_document(cmd.handle().await),
+            Commands::Search(cmd) => to_document(cmd.handle().await),
+            Commands::Add(cmd) => to_document(cmd.handle().await),
+            Commands::Remove(cmd) => to_document(cmd.handle().await),
+            Commands::Disable(cmd) => to_document(cmd.handle().await),
+            Commands::Enable(cmd) => to_document(cmd.handle().await),
+            Commands::Pin(cmd) => to_document(cmd.handle().await),
+            Commands::Unpin(cmd) => to_document(cmd.handle().await),
+            Commands::Bisect(cmd) => to_document(cmd.handle().await),
+            Commands::Rollback(cmd) => to_document(cmd.handle().await),
+            Commands::History(cmd) => to_document(cmd.handle()),
+            Commands::Config(cmd) => cmd.handle(),
+            Commands::Convert(cmd) => to_document(cmd.handle()),
+            Commands::Doctor(cmd) => to_document(cmd.handle().await),
+            Commands::CheckMc(cmd) => to_document(cmd.handle().await),
+            Commands::Migrate(cmd) => to_document(cmd.handle().await),
+        };
+        report(name, result)
+    }
+
+    pub fn name(&self) -> &'static str {
         match self {
-            Commands::Init(cmd) => cmd.handle().await,
-            Commands::Install(cmd) => cmd.handle().await,
-            Commands::Upgrade(cmd) => cmd.handle().await,
-            Commands::Outdated(cmd) => cmd.handle().await,
-            Commands::List(cmd) => cmd.handle(),
-            Commands::Search(cmd) => cmd.handle().await,
-            Commands::Add(cmd) => cmd.handle().await,
-            Commands::Remove(cmd) => cmd.handle().await,
+            Commands::Init(_) => "init",
+            Commands::Install(_) => "install",
+            Commands::Upgrade(_) => "upgrade",
+            Commands::Outdated(_) => "outdated",
+            Commands::List(_) => "list",
+            Commands::Search(_) => "search",
+            Commands::Add(_) => "add",
+            Commands::Remove(_) => "remove",
+            Commands::Disable(_) => "disable",
+            Commands::Enable(_) => "enable",
+            Commands::Pin(_) => "pin",
+            Commands::Unpin(_) => "unpin",
+            Commands::Bisect(_) => "bisect",
+            Commands::Rollback(_) => "rollback",
+            Commands::History(_) => "history",
+            Commands::Config(_) => "config",
+            Commands::Convert(_) => "convert",
+            Commands::Doctor(_) => "doctor",
+            Commands::CheckMc(_) => "check-mc",
+            Commands::Migrate(_) => "migrate",
         }
     }
 }
@@ -1,6 +1,11 @@
-use crate::app::modules::{
-    core::outdated::{Outdated, OutdatedResult},
-    io::{use_io, IOInstance},
+use crate::app::{
+    commands::output::is_text,
+    helpers::as_str,
+    modules::{
+        core::outdated::{Outdated, OutdatedResult},
+        io::{use_io, IOInstance},
+    },
+    McpmError,
 };
 use clap::Args;
 
@@ -11,39 +16,70 @@ pub struct OutdatedCommand {
 }
 
 impl OutdatedCommand {
-    pub async fn handle(&self) {
-        let io = use_io();
-
-        match Outdated::run(self.mods.clone()).await {
-            Ok(result) => Self::print_result(&io, result),
-            Err(msg) => io.error(&msg, None),
-        }
+    pub async fn handle(&self) -> Result<OutdatedResult, McpmError> {
+        let result = Outdated::run(self.mods.clone()).await?;
+        Self::print_result(&use_io(), &result);
+        Ok(result)
     }
 
-    fn print_result(io: &IOInstance, result: OutdatedResult) {
+    fn print_result(io: &IOInstance, result: &OutdatedResult) {
+        if let Some(l) = &result.loader {
+            io.info(&format!(
+                "Loader {} {} → {} available{}",
+                as_str(&l.name),
+                l.current,
+                l.latest,
+                if l.pinned {
+                    " (set by loader_version)"
+                } else {
+                    ""
+                }
+            ));
+        }
         if result.outdated.is_empty() {
             io.success("All mods are up to date");
             return;
         }
+        if !is_text() {
+            return;
+        }
 
         io.info(&format!(
             "Found {} outdated mod(s):\n",
             result.outdated.len()
         ));
         println!(
-            "| {:<30} | {:<20} | {:<20} | {:<20} |",
-            "Mod", "Current", "Wanted", "Latest"
+            "| {:<30} | {:<20} | {:<20} | {:<20} | {:<20} |",
+            "Mod", "Current", "Wanted", "Latest", "Latest (any)"
         );
-        println!("{}", "-".repeat(103));
+        println!("{}", "-".repeat(126));
 
-        for entry in result.outdated {
+        let mut reasons = vec![];
+        for entry in &result.outdated {
+            let wanted = entry.wanted.clone().unwrap_or("-".into());
+            let wanted = if entry.pinned {
+                format!("{} (pinned)", wanted)
+            } else {
+                wanted
+            };
             println!(
-                "| {:<30} | {:<20} | {:<20} | {:<20} |",
+                "| {:<30} | {:<20} | {:<20} | {:<20} | {:<20} |",
                 entry.key,
                 entry.current,
-                entry.wanted.unwrap_or("-".into()),
-                entry.latest.unwrap_or("-".into()),
+                wanted,
+                entry.latest.as_deref().unwrap_or("-"),
+                entry.latest_any.as_deref().unwrap_or("-"),
             );
+            if let Some(reason) = &entry.pin_reason {
+                reasons.push((&entry.key, reason));
+            }
+        }
+
+        if !reasons.is_empty() {
+            println!("\nPinned:");
+            for (key, reason) in reasons {
+                println!("  {}: {}", key, reason);
+            }
         }
 
         println!("\nChecked {} mods total", result.total_checked);
@@ -0,0 +1,62 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::app::{modules::io::use_io, Config, McpmError};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    /// Human readable, coloured output
+    #[default]
+    Text,
+    /// One JSON result document per command on stdout
+    Json,
+}
+
+/// True when the command should print its human readable output
+pub fn is_text() -> bool {
+    Config::get().output == OutputFormat::Text
+}
+
+/// Convert a command's result into the document printed in JSON mode
+pub fn to_document<T: Serialize>(result: Result<T, McpmError>) -> Result<Value, McpmError> {
+    result.map(|r| serde_json::to_value(r).expect("result documents serialize"))
+}
+
+/// Print the outcome of `command` and return the process exit code
+pub fn report(command: &str, result: Result<Value, McpmError>) -> i32 {
+    let code = match &result {
+        Ok(_) => 0,
+        Err(e) => e.exit_code(),
+    };
+
+    match (Config::get().output, result) {
+        (OutputFormat::Json, Ok(doc)) => print_json(&json!({
+            "command": command,
+            "ok": true,
+            "result": doc,
+        })),
+        (OutputFormat::Json, Err(e)) => print_json(&json!({
+            "command": command,
+            "ok": false,
+            "error": e,
+        })),
+        (OutputFormat::Text, Ok(_)) => {}
+        (OutputFormat::Text, Err(e)) => {
+            let io = use_io();
+            io.error(&format!("{} failed", command), Some(&e));
+            if let McpmError::HashMismatch { .. } = e {
+                io.info("Run with --force-rehash to download the file again");
+            }
+        }
+    }
+
+    code
+}
+
+fn print_json(doc: &Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(doc).expect("result documents serialize")
+    );
+}
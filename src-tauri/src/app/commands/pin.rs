@@ -0,0 +1,53 @@
+use crate::app::{
+    modules::{core::pin::Pin, io::use_io, manifest::models::Provider},
+    McpmError,
+};
+use clap::Args;
+use serde_json::{json, Value};
+
+#[derive(Args)]
+pub struct PinCommand {
+    pub slug: String,
+
+    /// Version to pin. Defaults to the locked version.
+    pub version: Option<String>,
+
+    /// Why the mod is held back, shown by `mcpm outdated`
+    #[arg(long)]
+    pub reason: Option<String>,
+
+    #[arg(long)]
+    pub provider: Option<Provider>,
+}
+
+#[derive(Args)]
+pub struct UnpinCommand {
+    pub slug: String,
+
+    #[arg(long)]
+    pub provider: Option<Provider>,
+}
+
+impl PinCommand {
+    pub async fn handle(&self) -> Result<Value, McpmError> {
+        let version = Pin::pin(
+            self.slug.clone(),
+            self.provider.clone(),
+            self.version.clone(),
+            self.reason.clone(),
+        )
+        .await?;
+
+        use_io().success(&format!("Pinned '{}' at {}", self.slug, version));
+        Ok(json!({ "slug": self.slug, "version": version }))
+    }
+}
+
+impl UnpinCommand {
+    pub async fn handle(&self) -> Result<(), McpmError> {
+        Pin::unpin(self.slug.clone(), self.provider.clone()).await?;
+
+        use_io().success(&format!("Unpinned '{}'", self.slug));
+        Ok(())
+    }
+}
@@ -1,4 +1,7 @@
-use crate::app::modules::{core::remove::Remove, io::use_io, manifest::models::Provider};
+use crate::app::{
+    modules::{core::remove::Remove, io::use_io, manifest::models::Provider},
+    McpmError,
+};
 use clap::Args;
 
 #[derive(Args)]
@@ -8,13 +11,11 @@ pub struct RemoveCommand {
 }
 
 impl RemoveCommand {
-    pub async fn handle(&self) {
+    pub async fn handle(&self) -> Result<(), McpmError> {
         let io = use_io();
 
-        match Remove::run(self.slug.clone(), self.provider.clone()).await {
-            Ok(None) => io.success(&format!("Removed mod '{}' and updated lockfile", self.slug)),
-            Ok(Some(w)) => io.warn(&w),
-            Err(e) => io.error(&e, None),
-        }
+        Remove::run(self.slug.clone(), self.provider.clone()).await?;
+        io.success(&format!("Removed mod '{}' and updated lockfile", self.slug));
+        Ok(())
     }
 }
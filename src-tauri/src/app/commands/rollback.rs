@@ -0,0 +1,24 @@
+use crate::app::{
+    modules::{core::rollback::Rollback, io::use_io, snapshot::models::Snapshot},
+    McpmError,
+};
+use clap::Args;
+
+#[derive(Args)]
+pub struct RollbackCommand {
+    /// Snapshot to restore (see `mcpm history`). Defaults to the latest one.
+    #[arg(long)]
+    pub to: Option<String>,
+}
+
+impl RollbackCommand {
+    pub async fn handle(&self) -> Result<Snapshot, McpmError> {
+        let snapshot = Rollback::run(self.to.clone()).await?;
+
+        use_io().success(&format!(
+            "Rolled back to snapshot {} ({})",
+            snapshot.id, snapshot.created_at
+        ));
+        Ok(snapshot)
+    }
+}
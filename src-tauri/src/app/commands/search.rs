@@ -1,4 +1,8 @@
-use crate::app::modules::core::search::Search;
+use crate::app::{
+    commands::output::is_text,
+    modules::{core::search::Search, repositories::models::ModResult},
+    McpmError,
+};
 use clap::Args;
 
 #[derive(Args)]
@@ -9,7 +13,14 @@ pub struct SearchCommand {
 }
 
 impl SearchCommand {
-    pub async fn handle(&self) {
-        Search::run(self.query.clone(), self.page.clone()).await
+    pub async fn handle(&self) -> Result<Vec<ModResult>, McpmError> {
+        let results = Search::run(self.query.clone(), self.page).await?;
+
+        if is_text() {
+            for r in &results {
+                println!("[{}] {} - {}", r.source, r.name, r.url);
+            }
+        }
+        Ok(results)
     }
 }
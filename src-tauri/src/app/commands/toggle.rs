@@ -0,0 +1,51 @@
+use crate::app::{
+    modules::{core::toggle::Toggle, io::use_io, manifest::models::Provider},
+    McpmError,
+};
+use clap::Args;
+
+#[derive(Args)]
+pub struct DisableCommand {
+    pub slug: String,
+
+    #[arg(long)]
+    pub provider: Option<Provider>,
+}
+
+#[derive(Args)]
+pub struct EnableCommand {
+    pub slug: String,
+
+    #[arg(long)]
+    pub provider: Option<Provider>,
+}
+
+impl DisableCommand {
+    pub async fn handle(&self) -> Result<(), McpmError> {
+        handle_toggle(&self.slug, &self.provider, true).await
+    }
+}
+
+impl EnableCommand {
+    pub async fn handle(&self) -> Result<(), McpmError> {
+        handle_toggle(&self.slug, &self.provider, false).await
+    }
+}
+
+async fn handle_toggle(
+    slug: &str,
+    provider: &Option<Provider>,
+    disabled: bool,
+) -> Result<(), McpmError> {
+    let io = use_io();
+    let state = if disabled { "Disabled" } else { "Enabled" };
+
+    match Toggle::run(slug.to_string(), provider.clone(), disabled).await? {
+        None => io.success(&format!(
+            "{} mod '{}'. Run `mcpm install` to apply",
+            state, slug
+        )),
+        Some(w) => io.warn(&w),
+    }
+    Ok(())
+}
@@ -1,6 +1,13 @@
-use crate::app::modules::{
-    core::{install::Install, upgrade::Upgrade},
-    io::use_io,
+use crate::app::{
+    commands::install::{print_left_out, print_plan},
+    modules::{
+        core::{
+            install::Install,
+            upgrade::{Upgrade, UpgradeOutput, UpgradeResult},
+        },
+        io::{use_io, IOInstance},
+    },
+    McpmError,
 };
 use clap::Args;
 
@@ -14,43 +21,90 @@ pub struct UpgradeCommand {
 
     #[arg(long)]
     pub force_rehash: bool,
+
+    /// Print what would be upgraded and installed without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Optional mods to install, by slug or `provider:slug`. Later installs keep them
+    #[arg(long, value_name = "MOD")]
+    pub with: Vec<String>,
 }
 
 impl UpgradeCommand {
-    pub async fn handle(&self) {
+    pub async fn handle(&self) -> Result<UpgradeOutput, McpmError> {
         let io = use_io();
 
-        match Upgrade::run(&self.mods).await {
-            Ok(result) => {
-                if result.upgraded.is_empty() {
-                    io.info("All selected mods are already up to date");
-                    return;
-                }
-
-                for (key, before, after) in result.upgraded {
-                    io.success(&format!(
-                        "Upgraded {}: {} → {}",
-                        key,
-                        before.unwrap_or_else(|| "-".into()),
-                        after.unwrap_or_else(|| "-".into())
-                    ));
-                }
-
-                if result.unchanged > 0 {
-                    io.info(&format!(
-                        "{} mod(s) were already up to date",
-                        result.unchanged
-                    ));
-                }
-
-                if let Err(e) = Install::run(self.no_cache, self.force_rehash).await {
-                    io.error(&format!("Installation failed after upgrade: {}", e), None);
-                    return;
-                }
-
-                io.success("All upgraded mods installed successfully.");
-            }
-            Err(msg) => io.error(&msg, None),
+        // Resolve and install in one go so the snapshot taken by the
+        // install still holds the pre-upgrade lock.
+        let (result, plan) =
+            Upgrade::plan(&self.mods, self.no_cache, self.force_rehash, &self.with).await?;
+
+        let verb = if self.dry_run {
+            "Would upgrade"
+        } else {
+            "Upgraded"
+        };
+        if !Self::print_result(&io, &result, verb) {
+            return Ok(UpgradeOutput {
+                result,
+                install: None,
+            });
+        }
+
+        if self.dry_run {
+            print_plan(&io, &plan);
+        } else {
+            Install::execute(&plan).await?;
+            io.success("All upgraded mods installed successfully.");
+            print_left_out(&io, &plan);
+        }
+
+        Ok(UpgradeOutput {
+            result,
+            install: Some(plan),
+        })
+    }
+
+    /// Returns false when nothing was upgraded
+    fn print_result(io: &IOInstance, result: &UpgradeResult, verb: &str) -> bool {
+        if !result.pinned.is_empty() {
+            io.info(&format!(
+                "Skipped {} pinned mod(s): {}",
+                result.pinned.len(),
+                result.pinned.join(", ")
+            ));
+        }
+        if !result.held.is_empty() {
+            io.info(&format!(
+                "Skipped {} mod(s) held by their version_id: {}",
+                result.held.len(),
+                result.held.join(", ")
+            ));
         }
+
+        if result.upgraded.is_empty() {
+            io.info("All selected mods are already up to date");
+            return false;
+        }
+
+        for c in &result.upgraded {
+            io.success(&format!(
+                "{} {}: {} → {}",
+                verb,
+                c.key,
+                c.before.as_deref().unwrap_or("-"),
+                c.after.as_deref().unwrap_or("-")
+            ));
+        }
+
+        if result.unchanged > 0 {
+            io.info(&format!(
+                "{} mod(s) were already up to date",
+                result.unchanged
+            ));
+        }
+
+        true
     }
 }
@@ -0,0 +1,193 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+    use tempfile::tempdir;
+    use toml_edit::DocumentMut;
+
+    use crate::app::{
+        commands::output::OutputFormat,
+        config::{set_key, Config, ConfigFile, ConfigLayers, ConfigSource},
+        McpmError,
+    };
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    fn layers(layers: Vec<(ConfigSource, ConfigFile)>) -> ConfigLayers {
+        ConfigLayers { layers }
+    }
+
+    #[test]
+    fn later_layers_win_and_tables_merge_per_key() {
+        let global = ConfigFile::parse(
+            r#"
+            concurrency = 2
+            proxy = "http://proxy:3128"
+            [mirrors]
+            modrinth = "https://mirror.example/v2"
+            [api_keys]
+            modrinth = "global-key"
+            "#,
+            None,
+        )
+        .unwrap();
+        let project = ConfigFile::parse(
+            r#"
+            name = "Pack"
+            [config]
+            concurrency = 6
+            [config.api_keys]
+            modrinth = "project-key"
+            "#,
+            Some("config"),
+        )
+        .unwrap();
+        let env = ConfigFile::from_env(vars(&[("MCPM_OFFLINE", "yes")])).unwrap();
+        let cli = ConfigFile {
+            concurrency: Some(1),
+            ..Default::default()
+        };
+
+        let layers = layers(vec![
+            (ConfigSource::Global, global),
+            (ConfigSource::Project, project),
+            (ConfigSource::Env, env),
+            (ConfigSource::Cli, cli),
+        ]);
+        let config = Config::from_layers(
+            false,
+            false,
+            OutputFormat::Text,
+            PathBuf::from("/pack/mcpm.json"),
+            layers,
+        )
+        .unwrap();
+
+        assert_eq!(config.concurrency, 1);
+        assert!(config.offline);
+        assert_eq!(config.proxy.as_deref(), Some("http://proxy:3128"));
+        assert_eq!(config.api_keys["modrinth"], "project-key");
+        assert_eq!(config.mirrors["modrinth"], "https://mirror.example/v2");
+
+        let sources: Vec<_> = config
+            .layers
+            .entries()
+            .into_iter()
+            .map(|(key, _, source)| (key, source))
+            .collect();
+        assert!(sources.contains(&("concurrency".into(), ConfigSource::Cli)));
+        assert!(sources.contains(&("api_keys.modrinth".into(), ConfigSource::Project)));
+        assert!(sources.contains(&("mirrors.modrinth".into(), ConfigSource::Global)));
+    }
+
+    #[test]
+    fn env_vars_map_to_keys() {
+        let env = ConfigFile::from_env(vars(&[
+            ("MCPM_CACHE_DIR", "/tmp/cache"),
+            ("MCPM_CONCURRENCY", "8"),
+            ("MCPM_DEFAULT_PROVIDER", "Modrinth"),
+            ("MCPM_MODRINTH_API_KEY", "secret"),
+            ("MCPM_MODRINTH_MIRROR", "https://mirror.example/v2"),
+            ("MCPM_SOMETHING_ELSE", "ignored"),
+            ("PATH", "/usr/bin"),
+        ]))
+        .unwrap();
+
+        assert_eq!(env.cache_dir.as_deref(), Some("/tmp/cache"));
+        assert_eq!(env.concurrency, Some(8));
+        assert!(env.default_provider.is_some());
+        assert_eq!(env.api_keys["modrinth"], "secret");
+        assert_eq!(env.mirrors["modrinth"], "https://mirror.example/v2");
+
+        let err = ConfigFile::from_env(vars(&[("MCPM_CONCURRENCY", "0")])).unwrap_err();
+        assert!(matches!(err, McpmError::InvalidInput(ref m) if m.starts_with("MCPM_CONCURRENCY")));
+    }
+
+    #[test]
+    fn set_key_keeps_comments_and_types_values() {
+        let mut doc: DocumentMut = "# team settings\nconcurrency = 2 # slow link\n"
+            .parse()
+            .unwrap();
+
+        set_key(doc.as_table_mut(), "concurrency", "8").unwrap();
+        set_key(doc.as_table_mut(), "offline", "true").unwrap();
+        set_key(doc.as_table_mut(), "api_keys.modrinth", "secret").unwrap();
+
+        let content = doc.to_string();
+        assert!(content.starts_with("# team settings\nconcurrency = 8 # slow link\n"));
+        assert!(content.contains("offline = true"));
+
+        let file = ConfigFile::parse(&content, None).unwrap();
+        assert_eq!(file.concurrency, Some(8));
+        assert_eq!(file.api_keys["modrinth"], "secret");
+
+        assert!(set_key(doc.as_table_mut(), "concurency", "8").is_err());
+        assert!(set_key(doc.as_table_mut(), "default_provider", "npm").is_err());
+        assert!(set_key(doc.as_table_mut(), "proxy", "not a url").is_err());
+    }
+
+    #[test]
+    fn unknown_keys_in_files_are_errors() {
+        assert!(ConfigFile::parse("concurency = 2", None).is_err());
+        assert!(ConfigFile::parse("[config]\nofline = true", Some("config")).is_err());
+        // Other tables of mcpm.toml are not config
+        assert!(ConfigFile::parse("[mods]\nsodium = \"^0.6\"", Some("config")).is_ok());
+    }
+
+    #[test]
+    fn manifest_is_found_in_parent_directories() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let nested = root.join("mods").join("extra");
+        fs::create_dir_all(&nested).unwrap();
+
+        // No manifest yet: a new project starts in the working directory
+        assert_eq!(
+            Config::locate_manifest(None, &nested),
+            nested.join("mcpm.json")
+        );
+
+        fs::write(root.join("mcpm.json"), "{}").unwrap();
+        assert_eq!(
+            Config::locate_manifest(None, &nested),
+            root.join("mcpm.json")
+        );
+
+        // --manifest takes a file or the directory holding it, relative to the working directory
+        assert_eq!(
+            Config::locate_manifest(Some("../.."), &nested),
+            root.canonicalize().unwrap().join("mcpm.json")
+        );
+        assert_eq!(
+            Config::locate_manifest(Some("other.json"), &nested),
+            nested.join("other.json")
+        );
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_manifest() {
+        let project = ConfigFile {
+            output_dir: Some("server".into()),
+            mods_dir: Some("plugins".into()),
+            cache_dir: Some(".cache".into()),
+            ..Default::default()
+        };
+        let config = Config::from_layers(
+            false,
+            false,
+            OutputFormat::Text,
+            PathBuf::from("/pack/mcpm.json"),
+            layers(vec![(ConfigSource::Project, project)]),
+        )
+        .unwrap();
+
+        assert_eq!(config.project_dir, PathBuf::from("/pack"));
+        assert_eq!(config.output_dir, PathBuf::from("/pack/server"));
+        assert_eq!(config.mods_dir, PathBuf::from("/pack/server/plugins"));
+        assert_eq!(config.cache_dir, PathBuf::from("/pack/.cache"));
+    }
+}
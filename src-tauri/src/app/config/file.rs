@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::{collections::BTreeMap, fs, io, path::Path};
+use toml_edit::DocumentMut;
+
+use crate::app::{modules::manifest::models::Provider, McpmError};
+
+/// One layer of settings, as written in a config file or taken from the environment.
+/// Every field is optional so layers can be stacked, see [`ConfigFile::merge`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConfigFile {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mods_dir: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_provider: Option<Provider>,
+    /// Downloads running at the same time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub concurrency: Option<usize>,
+    /// Proxy URL used for every request, e.g. `http://proxy:3128`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<String>,
+    /// Fail instead of touching the network, only cached files are used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offline: Option<bool>,
+    /// API key per provider, e.g. `api_keys.modrinth`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub api_keys: BTreeMap<String, String>,
+    /// API base URL per provider, e.g. `mirrors.modrinth`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub mirrors: BTreeMap<String, String>,
+}
+
+/// Keys holding a table of provider names to strings
+const TABLES: [&str; 2] = ["api_keys", "mirrors"];
+
+impl ConfigFile {
+    /// Read a config file, `Ok(None)` when it doesn't exist.
+    /// With `table` only that table of the file is read, e.g. `[config]` in `mcpm.toml`.
+    pub fn load(path: &Path, table: Option<&str>) -> Result<Option<Self>, McpmError> {
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => {
+                return Err(McpmError::io(
+                    format!("Failed to read {}", path.display()),
+                    e,
+                ))
+            }
+        };
+        Self::parse(&content, table)
+            .map(Some)
+            .map_err(|source| McpmError::ConfigParse {
+                path: path.to_path_buf(),
+                source,
+            })
+    }
+
+    /// Parse the content of a config file, see [`ConfigFile::load`]
+    pub fn parse(content: &str, table: Option<&str>) -> Result<Self, toml::de::Error> {
+        match table {
+            None => toml::from_str(content),
+            Some(t) => {
+                let mut doc: toml::Table = toml::from_str(content)?;
+                match doc.remove(t) {
+                    Some(v) => v.try_into(),
+                    None => Ok(Self::default()),
+                }
+            }
+        }
+    }
+
+    /// Build a layer from `MCPM_*` environment variables, e.g. `MCPM_CONCURRENCY`
+    /// or `MCPM_MODRINTH_API_KEY`. Other variables are ignored.
+    pub fn from_env(vars: impl IntoIterator<Item = (String, String)>) -> Result<Self, McpmError> {
+        let mut doc = DocumentMut::new();
+        for (name, raw) in vars {
+            let Some(key) = env_key(&name) else { continue };
+            set_key(doc.as_table_mut(), &key, &raw)
+                .map_err(|e| McpmError::InvalidInput(format!("{}: {}", name, e)))?;
+        }
+        toml::from_str(&doc.to_string())
+            .map_err(|e| McpmError::InvalidInput(format!("Environment: {}", e.message())))
+    }
+
+    /// Stack `other` on top of `self`, values set in `other` win
+    pub fn merge(mut self, other: Self) -> Self {
+        self.cache_dir = other.cache_dir.or(self.cache_dir);
+        self.output_dir = other.output_dir.or(self.output_dir);
+        self.mods_dir = other.mods_dir.or(self.mods_dir);
+        self.default_provider = other.default_provider.or(self.default_provider);
+        self.concurrency = other.concurrency.or(self.concurrency);
+        self.proxy = other.proxy.or(self.proxy);
+        self.offline = other.offline.or(self.offline);
+        self.api_keys.extend(other.api_keys);
+        self.mirrors.extend(other.mirrors);
+        self
+    }
+
+    /// The values set in this layer as dotted keys, e.g. `("mirrors.modrinth", "https://…")`
+    pub fn entries(&self) -> Vec<(String, String)> {
+        let Ok(Value::Object(fields)) = serde_json::to_value(self) else {
+            return vec![];
+        };
+
+        let mut entries = vec![];
+        for (key, value) in fields {
+            match value {
+                Value::Object(table) => {
+                    for (name, v) in table {
+                        entries.push((format!("{}.{}", key, name), display(&v)));
+                    }
+                }
+                v => entries.push((key, display(&v))),
+            }
+        }
+        entries
+    }
+}
+
+/// Set `key` to `raw` in a config document, keeping the rest of the document as is.
+/// The value is typed by the key, so `concurrency = 8` is written as a number.
+pub fn set_key(table: &mut toml_edit::Table, key: &str, raw: &str) -> Result<(), String> {
+    let value: toml_edit::Value = match key {
+        "concurrency" => match raw.parse::<i64>() {
+            Ok(n) if n > 0 => n.into(),
+            _ => return Err(format!("expected a positive number, got '{}'", raw)),
+        },
+        "offline" => match raw.to_lowercase().as_str() {
+            "true" | "1" | "yes" => true.into(),
+            "false" | "0" | "no" => false.into(),
+            _ => return Err(format!("expected true or false, got '{}'", raw)),
+        },
+        "default_provider" => {
+            let name = raw.to_lowercase();
+            name.parse::<Provider>()
+                .map_err(|_| format!("unknown provider '{}'", raw))?;
+            name.into()
+        }
+        "proxy" => {
+            reqwest::Proxy::all(raw).map_err(|_| format!("invalid proxy URL '{}'", raw))?;
+            raw.into()
+        }
+        "cache_dir" | "output_dir" | "mods_dir" => raw.into(),
+        _ => match key.split_once('.') {
+            Some((t, name)) if is_key(key) => {
+                let sub = table
+                    .entry(t)
+                    .or_insert_with(toml_edit::table)
+                    .as_table_mut()
+                    .ok_or_else(|| format!("'{}' is not a table", t))?;
+                replace(sub, name, raw.into());
+                return Ok(());
+            }
+            _ => return Err(format!("unknown config key '{}'", key)),
+        },
+    };
+
+    replace(table, key, value);
+    Ok(())
+}
+
+/// Insert or overwrite a value, keeping the comments around an existing one
+fn replace(table: &mut toml_edit::Table, key: &str, mut value: toml_edit::Value) {
+    match table.get_mut(key).and_then(|item| item.as_value_mut()) {
+        Some(old) => {
+            *value.decor_mut() = old.decor().clone();
+            *old = value;
+        }
+        None => {
+            table.insert(key, toml_edit::Item::Value(value));
+        }
+    }
+}
+
+/// Whether `key` names a setting, e.g. `concurrency` or `api_keys.modrinth`
+pub fn is_key(key: &str) -> bool {
+    match key.split_once('.') {
+        Some((t, name)) => TABLES.contains(&t) && !name.is_empty() && !name.contains('.'),
+        None => matches!(
+            key,
+            "cache_dir"
+                | "output_dir"
+                | "mods_dir"
+                | "default_provider"
+                | "concurrency"
+                | "proxy"
+                | "offline"
+        ),
+    }
+}
+
+/// Config key of an environment variable, e.g. `MCPM_MODRINTH_MIRROR` → `mirrors.modrinth`
+fn env_key(name: &str) -> Option<String> {
+    let rest = name.strip_prefix("MCPM_")?;
+    let key = match rest {
+        "CACHE_DIR" | "OUTPUT_DIR" | "MODS_DIR" | "DEFAULT_PROVIDER" | "CONCURRENCY" | "PROXY"
+        | "OFFLINE" => rest.to_lowercase(),
+        _ => {
+            if let Some(p) = rest.strip_suffix("_API_KEY") {
+                format!("api_keys.{}", p.to_lowercase())
+            } else if let Some(p) = rest.strip_suffix("_MIRROR") {
+                format!("mirrors.{}", p.to_lowercase())
+            } else {
+                return None;
+            }
+        }
+    };
+    Some(key)
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        v => v.to_string(),
+    }
+}
@@ -0,0 +1,279 @@
+mod file;
+
+use dirs;
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+};
+
+use crate::app::{
+    commands::{output::OutputFormat, Cli},
+    modules::manifest::{models::Provider, MANIFEST_FILES},
+    McpmError,
+};
+
+pub use file::{is_key, set_key, ConfigFile};
+
+/// Per-user config file, relative to the user's config directory
+const GLOBAL_FILE: &str = "mcpm/config.toml";
+/// Project config files next to the manifest, the first one found is used.
+/// `mcpm.toml` only contributes its `[config]` table.
+pub const PROJECT_FILES: [(&str, Option<&str>); 2] =
+    [(".mcpmrc", None), ("mcpm.toml", Some("config"))];
+
+const DEFAULT_CONCURRENCY: usize = 4;
+
+#[derive(Clone, Debug)]
+pub struct Config {
+    pub verbose: bool,
+    pub quiet: bool,
+    pub output: OutputFormat,
+    /// The project's manifest, found by `--manifest` or by walking up from the working directory
+    pub manifest_path: PathBuf,
+    /// Directory of the manifest. Relative paths are resolved against it.
+    pub project_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    pub output_dir: PathBuf,
+    pub mods_dir: PathBuf,
+    pub default_provider: Provider,
+    /// Downloads running at the same time
+    pub concurrency: usize,
+    pub api_keys: BTreeMap<String, String>,
+    pub proxy: Option<String>,
+    /// API base URL per provider
+    pub mirrors: BTreeMap<String, String>,
+    pub offline: bool,
+    /// The layers the values above were taken from, for `mcpm config`
+    pub layers: ConfigLayers,
+}
+
+/// Where a config value comes from, later sources override earlier ones
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSource {
+    Default,
+    Global,
+    Project,
+    Env,
+    Cli,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    pub layers: Vec<(ConfigSource, ConfigFile)>,
+}
+
+static CONFIG: OnceCell<Config> = OnceCell::new();
+
+impl Config {
+    pub fn init(cli: &Cli) -> Result<(), McpmError> {
+        let cwd = env::current_dir()
+            .map_err(|e| McpmError::io("Failed to read the working directory", e))?;
+        let manifest_path = Self::locate_manifest(cli.manifest.as_deref(), &cwd);
+        let layers = ConfigLayers::load(cli, project_dir_of(&manifest_path))?;
+
+        CONFIG
+            .set(Self::from_layers(
+                cli.verbose,
+                cli.quiet,
+                cli.output,
+                manifest_path,
+                layers,
+            )?)
+            .ok();
+        Ok(())
+    }
+
+    pub fn get() -> &'static Self {
+        CONFIG.get().expect("GlobalConfig not initialized")
+    }
+
+    /// Resolve the stacked layers into the effective configuration
+    pub fn from_layers(
+        verbose: bool,
+        quiet: bool,
+        output: OutputFormat,
+        manifest_path: PathBuf,
+        layers: ConfigLayers,
+    ) -> Result<Self, McpmError> {
+        let merged = layers.merged();
+
+        if let Some(proxy) = &merged.proxy {
+            reqwest::Proxy::all(proxy)
+                .map_err(|_| McpmError::InvalidInput(format!("Invalid proxy URL '{}'", proxy)))?;
+        }
+
+        let project_dir = project_dir_of(&manifest_path).to_path_buf();
+        let cache_dir = Self::resolve_cache_dir(merged.cache_dir, &project_dir);
+        let output_dir = Self::resolve_output_dir(merged.output_dir, &project_dir);
+        let mods_dir = Self::resolve_mods_dir(merged.mods_dir, &output_dir);
+
+        Ok(Self {
+            verbose,
+            quiet,
+            output,
+            manifest_path,
+            project_dir,
+            cache_dir,
+            output_dir,
+            mods_dir,
+            default_provider: merged.default_provider.unwrap_or(Provider::Modrinth),
+            concurrency: merged.concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1),
+            api_keys: merged.api_keys,
+            proxy: merged.proxy,
+            mirrors: merged.mirrors,
+            offline: merged.offline.unwrap_or(false),
+            layers,
+        })
+    }
+
+    /// The manifest to use: `explicit` (a file or a directory holding one), otherwise
+    /// the nearest `mcpm.json` or `mcpm.toml` in `cwd` or its parents. Without one,
+    /// a new project lives in `cwd`.
+    pub fn locate_manifest(explicit: Option<&str>, cwd: &Path) -> PathBuf {
+        let in_dir = |dir: &Path| {
+            MANIFEST_FILES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|p| p.is_file())
+        };
+
+        if let Some(path) = explicit {
+            let path = cwd.join(path);
+            let path = path.canonicalize().unwrap_or(path);
+            return if path.is_dir() {
+                in_dir(&path).unwrap_or_else(|| path.join(MANIFEST_FILES[0]))
+            } else {
+                path
+            };
+        }
+
+        cwd.ancestors()
+            .find_map(in_dir)
+            .unwrap_or_else(|| cwd.join(MANIFEST_FILES[0]))
+    }
+
+    /// The user's config file, `$XDG_CONFIG_HOME/mcpm/config.toml` or `~/.config/mcpm/config.toml`
+    pub fn global_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .filter(|v| !v.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|h| h.join(".config")))
+            .map(|dir| dir.join(GLOBAL_FILE))
+    }
+
+    /// The project config file in use and the table it is read from.
+    /// Defaults to `.mcpmrc` when there is none yet.
+    pub fn project_path(project_dir: &Path) -> (PathBuf, Option<&'static str>) {
+        PROJECT_FILES
+            .iter()
+            .map(|(name, table)| (project_dir.join(name), *table))
+            .find(|(path, _)| path.exists())
+            .unwrap_or_else(|| (project_dir.join(PROJECT_FILES[0].0), PROJECT_FILES[0].1))
+    }
+
+    fn resolve_cache_dir(cache: Option<String>, project_dir: &Path) -> PathBuf {
+        if let Some(c) = cache {
+            return project_dir.join(c);
+        }
+        if let Some(home) = dirs::home_dir() {
+            return home.join(".mcpm/cache");
+        }
+        project_dir.join(".mcpm/cache")
+    }
+
+    fn resolve_output_dir(output: Option<String>, project_dir: &Path) -> PathBuf {
+        match output {
+            Some(o) => project_dir.join(o),
+            None => project_dir.to_path_buf(),
+        }
+    }
+
+    fn resolve_mods_dir(mods: Option<String>, output_dir: &Path) -> PathBuf {
+        // Absolute or relative to the output directory
+        match mods {
+            Some(m) => output_dir.join(m),
+            None => output_dir.join("mods"),
+        }
+    }
+}
+
+/// Directory a manifest path lives in
+fn project_dir_of(manifest_path: &Path) -> &Path {
+    manifest_path.parent().unwrap_or(Path::new("."))
+}
+
+impl ConfigLayers {
+    /// Read every layer: built-in defaults, the global file, the project file,
+    /// `MCPM_*` environment variables and the CLI flags.
+    pub fn load(cli: &Cli, project_dir: &Path) -> Result<Self, McpmError> {
+        let mut layers = vec![(ConfigSource::Default, Self::defaults(project_dir))];
+
+        if let Some(path) = Config::global_path() {
+            if let Some(file) = ConfigFile::load(&path, None)? {
+                layers.push((ConfigSource::Global, file));
+            }
+        }
+
+        let (path, table) = Config::project_path(project_dir);
+        if let Some(file) = ConfigFile::load(&path, table)? {
+            layers.push((ConfigSource::Project, file));
+        }
+
+        layers.push((ConfigSource::Env, ConfigFile::from_env(env::vars())?));
+        layers.push((
+            ConfigSource::Cli,
+            ConfigFile {
+                cache_dir: cli.cache_dir.clone(),
+                output_dir: cli.output_dir.clone(),
+                mods_dir: cli.mods_dir.clone(),
+                offline: cli.offline.then_some(true),
+                ..Default::default()
+            },
+        ));
+
+        Ok(Self { layers })
+    }
+
+    /// Values used when no layer sets them
+    fn defaults(project_dir: &Path) -> ConfigFile {
+        let path = |p: PathBuf| p.to_string_lossy().to_string();
+        ConfigFile {
+            cache_dir: dirs::home_dir().map(|h| path(h.join(".mcpm/cache"))),
+            output_dir: Some(path(project_dir.to_path_buf())),
+            mods_dir: Some("mods".into()),
+            default_provider: Some(Provider::Modrinth),
+            concurrency: Some(DEFAULT_CONCURRENCY),
+            offline: Some(false),
+            ..Default::default()
+        }
+    }
+
+    pub fn merged(&self) -> ConfigFile {
+        self.layers
+            .iter()
+            .fold(ConfigFile::default(), |acc, (_, layer)| {
+                acc.merge(layer.clone())
+            })
+    }
+
+    /// Every effective value with the layer it comes from, sorted by key
+    pub fn entries(&self) -> Vec<(String, String, ConfigSource)> {
+        let mut effective = BTreeMap::new();
+        for (source, layer) in &self.layers {
+            for (key, value) in layer.entries() {
+                effective.insert(key, (value, *source));
+            }
+        }
+        effective
+            .into_iter()
+            .map(|(key, (value, source))| (key, value, source))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod config_spec;
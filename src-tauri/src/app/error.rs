@@ -0,0 +1,187 @@
+use std::path::PathBuf;
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use thiserror::Error;
+
+/// Errors returned by core operations, services and repositories.
+///
+/// Every variant maps to a stable process exit code, see [`McpmError::exit_code`].
+#[derive(Debug, Error)]
+pub enum McpmError {
+    #[error("Request to {url} failed")]
+    Network {
+        url: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// A provider answered with something that can't be read
+    #[error("Unexpected response from {url}")]
+    ProviderResponse {
+        url: String,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("{0} not found")]
+    NotFound(String),
+
+    #[error("No compatible versions found for '{slug}' ({detail})")]
+    NoCompatibleVersion { slug: String, detail: String },
+
+    #[error("Hash mismatch for {}", path.display())]
+    HashMismatch { path: PathBuf },
+
+    #[error("{context}")]
+    Io {
+        context: String,
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("Invalid manifest {}", path.display())]
+    ManifestParse {
+        path: PathBuf,
+        /// The JSON or TOML parse error
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Invalid lock file {}", path.display())]
+    LockParse {
+        path: PathBuf,
+        #[source]
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+
+    #[error("Invalid config file {}", path.display())]
+    ConfigParse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    /// Network access needed while offline mode is on
+    #[error("Offline mode is enabled, can't reach {url}")]
+    Offline { url: String },
+
+    /// The requested change clashes with the current state
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("{0}")]
+    InvalidInput(String),
+
+    /// A choice that needs the user, made while prompts are disabled
+    #[error("{prompt}: more than one candidate and prompts are disabled ({})", candidates.join(", "))]
+    Ambiguous {
+        prompt: String,
+        candidates: Vec<String>,
+    },
+
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+impl McpmError {
+    pub fn io(context: impl Into<String>, source: std::io::Error) -> Self {
+        Self::Io {
+            context: context.into(),
+            source,
+        }
+    }
+
+    pub fn network(url: impl Into<String>, source: reqwest::Error) -> Self {
+        Self::Network {
+            url: url.into(),
+            source,
+        }
+    }
+
+    pub fn provider_response(
+        url: impl Into<String>,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self::ProviderResponse {
+            url: url.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Exit code of the process when a command fails with this error.
+    /// These are part of the CLI contract, don't renumber them.
+    ///
+    /// | Code | Error                     |
+    /// |------|---------------------------|
+    /// | 1    | I/O failure               |
+    /// | 2    | Invalid input             |
+    /// | 3    | Not found                 |
+    /// | 4    | No compatible version     |
+    /// | 5    | Network failure           |
+    /// | 6    | Hash mismatch             |
+    /// | 7    | Invalid manifest or lock  |
+    /// | 8    | Conflict with the state   |
+    /// | 9    | Ambiguous choice          |
+    /// | 10   | Invalid config file       |
+    /// | 11   | Unreadable provider reply |
+    /// | 130  | Cancelled by the user     |
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::Io { .. } => 1,
+            Self::InvalidInput(_) => 2,
+            Self::NotFound(_) => 3,
+            Self::NoCompatibleVersion { .. } => 4,
+            Self::Network { .. } | Self::Offline { .. } => 5,
+            Self::HashMismatch { .. } => 6,
+            Self::ManifestParse { .. } | Self::LockParse { .. } => 7,
+            Self::Conflict(_) => 8,
+            Self::Ambiguous { .. } => 9,
+            Self::ConfigParse { .. } => 10,
+            Self::ProviderResponse { .. } => 11,
+            Self::Cancelled => 130,
+        }
+    }
+
+    /// Messages of the underlying errors, outermost first
+    pub fn causes(&self) -> Vec<String> {
+        let mut causes = vec![];
+        let mut source = std::error::Error::source(self);
+        while let Some(s) = source {
+            causes.push(s.to_string());
+            source = s.source();
+        }
+        causes
+    }
+
+    /// Stable name of the error class, used in JSON output
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Io { .. } => "io",
+            Self::InvalidInput(_) => "invalid_input",
+            Self::NotFound(_) => "not_found",
+            Self::NoCompatibleVersion { .. } => "no_compatible_version",
+            Self::Network { .. } => "network",
+            Self::ProviderResponse { .. } => "provider_response",
+            Self::Offline { .. } => "offline",
+            Self::HashMismatch { .. } => "hash_mismatch",
+            Self::ManifestParse { .. } => "manifest_parse",
+            Self::LockParse { .. } => "lock_parse",
+            Self::Conflict(_) => "conflict",
+            Self::Ambiguous { .. } => "ambiguous",
+            Self::ConfigParse { .. } => "config_parse",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// Serialized as `{ kind, message, causes, exit_code }` for JSON output and the GUI
+impl Serialize for McpmError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("McpmError", 4)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("causes", &self.causes())?;
+        state.serialize_field("exit_code", &self.exit_code())?;
+        state.end()
+    }
+}
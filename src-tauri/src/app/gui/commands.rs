@@ -0,0 +1,102 @@
+// Tauri commands, the GUI counterpart of `app::commands`.
+// Errors reach the frontend as `{ kind, message, causes, exit_code }`.
+use crate::app::{
+    modules::{
+        core::{
+            add::Add,
+            init::Init,
+            install::{Install, InstallPlan},
+            list::{List, ListEntry},
+            outdated::{Outdated, OutdatedResult},
+            remove::Remove,
+            search::Search,
+            upgrade::{Upgrade, UpgradeOutput},
+        },
+        manifest::models::{Channel, ModEntry, Provider},
+        repositories::models::ModResult,
+    },
+    McpmError,
+};
+
+#[tauri::command]
+pub async fn init() -> Result<(), McpmError> {
+    Init::run(None, false).await
+}
+
+#[tauri::command]
+pub async fn add(
+    id: String,
+    version: Option<String>,
+    provider: Option<Provider>,
+    exact: bool,
+    search: bool,
+    channel: Option<Channel>,
+) -> Result<ModEntry, McpmError> {
+    Add::run(&id, version, provider, exact, search, channel).await
+}
+
+#[tauri::command]
+pub async fn remove(slug: String, provider: Option<Provider>) -> Result<(), McpmError> {
+    Remove::run(slug, provider).await
+}
+
+#[tauri::command]
+pub async fn install(
+    no_cache: bool,
+    force_rehash: bool,
+    dry_run: bool,
+    with: Option<Vec<String>>,
+) -> Result<InstallPlan, McpmError> {
+    let with = with.unwrap_or_default();
+    if dry_run {
+        return Install::plan(no_cache, force_rehash, &with).await;
+    }
+    Install::run(no_cache, force_rehash, &with).await
+}
+
+#[tauri::command]
+pub async fn upgrade(
+    mods: Vec<String>,
+    no_cache: bool,
+    force_rehash: bool,
+    dry_run: bool,
+    with: Option<Vec<String>>,
+) -> Result<UpgradeOutput, McpmError> {
+    let (result, plan) =
+        Upgrade::plan(&mods, no_cache, force_rehash, &with.unwrap_or_default()).await?;
+    if result.upgraded.is_empty() {
+        return Ok(UpgradeOutput {
+            result,
+            install: None,
+        });
+    }
+
+    if !dry_run {
+        Install::execute(&plan).await?;
+    }
+    Ok(UpgradeOutput {
+        result,
+        install: Some(plan),
+    })
+}
+
+#[tauri::command]
+pub async fn outdated(mods: Vec<String>) -> Result<OutdatedResult, McpmError> {
+    Outdated::run(mods).await
+}
+
+#[tauri::command]
+pub async fn search(query: String, page: Option<usize>) -> Result<Vec<ModResult>, McpmError> {
+    Search::run(query, page).await
+}
+
+#[tauri::command]
+pub async fn list() -> Result<Vec<ListEntry>, McpmError> {
+    List::run().await
+}
+
+/// Answer a question sent with the `mcpm://question` event, `null` cancels it
+#[tauri::command]
+pub fn answer(id: u64, value: serde_json::Value) -> Result<(), McpmError> {
+    crate::app::gui::answer(id, value)
+}
@@ -0,0 +1,154 @@
+pub mod commands;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use futures::{
+    channel::{mpsc::UnboundedReceiver, oneshot},
+    StreamExt,
+};
+use once_cell::sync::{Lazy, OnceCell};
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Emitter};
+
+use crate::app::{
+    modules::io::gui::{GuiEvent, GuiRequest},
+    McpmError,
+};
+
+/// Name of the event carrying IO messages and progress to the frontend
+pub const IO_EVENT: &str = "mcpm://io";
+/// Name of the event asking the frontend to open a modal
+pub const QUESTION_EVENT: &str = "mcpm://question";
+
+static APP: OnceCell<AppHandle> = OnceCell::new();
+static REQUESTS: Mutex<Option<UnboundedReceiver<GuiRequest>>> = Mutex::new(None);
+static PENDING: Lazy<Mutex<HashMap<u64, Responder>>> = Lazy::new(Default::default);
+static NEXT_QUESTION: AtomicU64 = AtomicU64::new(0);
+
+/// A modal shown in the frontend, answered with the `answer` command
+#[derive(Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Question<'a> {
+    Prompt {
+        id: u64,
+        question: &'a str,
+        default: &'a Option<String>,
+    },
+    Confirm {
+        id: u64,
+        question: &'a str,
+        default: bool,
+    },
+    Select {
+        id: u64,
+        question: &'a str,
+        items: &'a [String],
+        default: usize,
+    },
+}
+
+/// Where the answer to an open question goes
+enum Responder {
+    Prompt(oneshot::Sender<Option<String>>),
+    Confirm(oneshot::Sender<Option<bool>>),
+    Select(oneshot::Sender<Option<usize>>),
+}
+
+/// Called from the Tauri setup hook, events are dropped before that
+pub fn set_app_handle(app: AppHandle) {
+    APP.set(app).ok();
+
+    if let Some(requests) = REQUESTS.lock().unwrap().take() {
+        tauri::async_runtime::spawn(forward_requests(requests));
+    }
+}
+
+/// Hand the questions of the IO over to the frontend once the app is running
+pub fn serve_requests(requests: UnboundedReceiver<GuiRequest>) {
+    *REQUESTS.lock().unwrap() = Some(requests);
+}
+
+pub fn emit_event(event: &GuiEvent) {
+    if let Some(app) = APP.get() {
+        let _ = app.emit(IO_EVENT, event);
+    }
+}
+
+async fn forward_requests(mut requests: UnboundedReceiver<GuiRequest>) {
+    while let Some(request) = requests.next().await {
+        let id = NEXT_QUESTION.fetch_add(1, Ordering::Relaxed);
+        let question = match &request {
+            GuiRequest::Prompt {
+                question, default, ..
+            } => Question::Prompt {
+                id,
+                question,
+                default,
+            },
+            GuiRequest::Confirm {
+                question, default, ..
+            } => Question::Confirm {
+                id,
+                question,
+                default: *default,
+            },
+            GuiRequest::Select {
+                question,
+                items,
+                default,
+                ..
+            } => Question::Select {
+                id,
+                question,
+                items,
+                default: *default,
+            },
+        };
+        let payload = serde_json::to_value(&question).expect("questions serialize");
+
+        let responder = match request {
+            GuiRequest::Prompt { respond, .. } => Responder::Prompt(respond),
+            GuiRequest::Confirm { respond, .. } => Responder::Confirm(respond),
+            GuiRequest::Select { respond, .. } => Responder::Select(respond),
+        };
+
+        // Without a window the responder is dropped, which cancels the question
+        let Some(app) = APP.get() else { continue };
+        PENDING.lock().unwrap().insert(id, responder);
+        if app.emit(QUESTION_EVENT, payload).is_err() {
+            PENDING.lock().unwrap().remove(&id);
+        }
+    }
+}
+
+/// Resolve an open question. A `null` answer cancels it, so does an answer of the wrong type.
+pub fn answer(id: u64, answer: Value) -> Result<(), McpmError> {
+    let responder = PENDING
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .ok_or_else(|| McpmError::NotFound(format!("Question {}", id)))?;
+
+    let invalid =
+        |e: serde_json::Error| McpmError::InvalidInput(format!("Answer to question {}: {}", id, e));
+    // A closed receiver means the command already gave up on the answer
+    match responder {
+        Responder::Prompt(tx) => {
+            let _ = tx.send(serde_json::from_value(answer).map_err(invalid)?);
+        }
+        Responder::Confirm(tx) => {
+            let _ = tx.send(serde_json::from_value(answer).map_err(invalid)?);
+        }
+        Responder::Select(tx) => {
+            let _ = tx.send(serde_json::from_value(answer).map_err(invalid)?);
+        }
+    }
+    Ok(())
+}
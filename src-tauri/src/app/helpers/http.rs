@@ -0,0 +1,23 @@
+use reqwest::{Client, Proxy};
+
+use crate::app::{Config, McpmError};
+
+/// HTTP client honoring the configured proxy
+pub fn client() -> Client {
+    let mut builder = Client::builder().user_agent(concat!("mcpm/", env!("CARGO_PKG_VERSION")));
+    if let Some(proxy) = &Config::get().proxy {
+        // The URL is validated when the config is loaded
+        builder = builder.proxy(Proxy::all(proxy).expect("proxy URL is valid"));
+    }
+    builder.build().expect("HTTP client can be built")
+}
+
+/// Fail with [`McpmError::Offline`] when offline mode is on
+pub fn ensure_online(url: &str) -> Result<(), McpmError> {
+    if Config::get().offline {
+        return Err(McpmError::Offline {
+            url: url.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -1,5 +1,13 @@
-pub mod cli;
+pub mod http;
+pub mod schema;
 pub mod semver;
+pub mod version;
+
+#[cfg(test)]
+mod semver_spec;
+#[cfg(test)]
+mod version_spec;
+
 use serde::Serialize;
 
 pub fn as_str<T: Serialize>(value: &T) -> String {
@@ -8,3 +16,19 @@ pub fn as_str<T: Serialize>(value: &T) -> String {
         .trim_matches('"')
         .to_string()
 }
+
+/// Human readable byte size, e.g. "1.4 MiB"
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
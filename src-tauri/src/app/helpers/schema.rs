@@ -0,0 +1,66 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// One step of a file format upgrade, from schema `from` to `from + 1`
+pub struct Migration {
+    pub from: u32,
+    /// What the step changes, shown in the migration report
+    pub description: &'static str,
+    pub apply: fn(&mut Map<String, Value>),
+}
+
+/// The steps applied to bring a file to the current schema
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub from: u32,
+    pub to: u32,
+    pub steps: Vec<String>,
+}
+
+impl MigrationReport {
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+/// Upgrade `value` to schema `current`, one step at a time.
+/// A file without `schema_version` is schema 0.
+pub fn migrate(
+    value: &mut Value,
+    current: u32,
+    steps: &[Migration],
+) -> Result<MigrationReport, String> {
+    let table = value
+        .as_object_mut()
+        .ok_or("expected a table at the top level")?;
+
+    let from = match table.get("schema_version") {
+        None => 0,
+        Some(v) => v
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or("schema_version must be a non-negative integer")?,
+    };
+    if from > current {
+        return Err(format!(
+            "schema_version {} is newer than this mcpm supports ({}), upgrade mcpm to read it",
+            from, current
+        ));
+    }
+
+    let mut report = MigrationReport {
+        from,
+        to: current,
+        steps: vec![],
+    };
+    for version in from..current {
+        let step = steps
+            .iter()
+            .find(|s| s.from == version)
+            .expect("a migration step for every older schema");
+        (step.apply)(table);
+        table.insert("schema_version".into(), Value::from(version + 1));
+        report.steps.push(step.description.to_string());
+    }
+    Ok(report)
+}
@@ -1,113 +1,238 @@
-use crate::app::modules::{manifest::models::VersionSpec, repositories::models::VersionResult};
-use semver::{Version, VersionReq};
+use super::version::ModVersion;
+use crate::app::modules::{
+    manifest::models::{Channel, VersionSpec},
+    repositories::models::VersionResult,
+};
+use semver::{Prerelease, Version, VersionReq};
 
+const OPERATORS: [&str; 7] = [">=", "<=", ">", "<", "=", "^", "~"];
+
+/// Whether a version spec is a range rather than one exact version:
+/// it has an operator, a wildcard part, `||` or a hyphen range
 pub fn is_semver_range(s: &str) -> bool {
-    matches!(s.chars().next(), Some('^' | '~' | '>' | '<' | '*'))
+    let s = s.trim();
+    s.contains("||")
+        || s.contains(" - ")
+        || s.starts_with(['^', '~', '>', '<', '=', '*'])
+        || s.split([' ', ',']).any(|t| {
+            let parts: Vec<&str> = t.split('.').collect();
+            parts.iter().any(|p| is_wildcard(p))
+                && parts
+                    .iter()
+                    .all(|p| is_wildcard(p) || p.parse::<u64>().is_ok())
+        })
 }
 
-pub fn resolve_version(range: &str, available: &[VersionResult]) -> Option<VersionResult> {
-    resolve_with_semver(range, available)
-        .or_else(|| resolve_with_normalized(range, available))
-        .or_else(|| resolve_with_exact(range, available))
+/// An npm-style version range: `^0.5 || >=0.7`, `1.2 - 1.4`, `1.20.x` or `=1.2.3`.
+/// A bare version matches exactly that version, a partial one (`1.20`) any patch of it.
+#[derive(Debug, Clone)]
+pub struct VersionRange {
+    alternatives: Vec<VersionReq>,
 }
 
-pub fn satisfies(spec: &VersionSpec, version: &str) -> bool {
-    match spec {
-        VersionSpec::Exact(v) => v == version,
-        VersionSpec::Range(r) => resolve_with_semver(
-            r,
-            &[VersionResult {
-                mod_id: String::new(),
-                version: version.to_string(),
-                minecraft_versions: vec![],
-                url: String::new(),
-                hash: String::new(),
-            }],
-        )
-        .is_some(),
+impl VersionRange {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let alternatives = spec
+            .split("||")
+            .map(|alt| {
+                let comparators = alternative(alt.trim())?;
+                VersionReq::parse(&comparators.join(", "))
+                    .map_err(|e| format!("invalid version range '{}': {}", spec, e))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { alternatives })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.alternatives.iter().any(|req| req.matches(version))
     }
 }
 
-/// Normalize a version string by stripping leading non-numeric characters
-/// Example: "mc1.21.1-0.6.5-fabric" → "0.6.5-fabric"
-fn normalize_version(s: &str) -> String {
-    let mut chars = s.chars();
-    let mut result = String::new();
-    let mut found_digit = false;
+/// Comparators of one `||` alternative, in the syntax of the `semver` crate
+fn alternative(alt: &str) -> Result<Vec<String>, String> {
+    if let Some((low, high)) = alt.split_once(" - ") {
+        // `<=1.4` already means below 1.5.0 for partial versions
+        return Ok(vec![
+            comparator(">=", low.trim())?,
+            comparator("<=", high.trim())?,
+        ]);
+    }
 
-    while let Some(c) = chars.next() {
-        if !found_digit && !c.is_ascii_digit() {
-            continue;
+    // Operators may be separated from their version: `>= 1.2`
+    let mut comparators = vec![];
+    let mut pending_op: Option<&str> = None;
+    for token in alt.split([' ', ',']).filter(|t| !t.is_empty()) {
+        let op = OPERATORS
+            .iter()
+            .find(|op| token.starts_with(**op))
+            .copied()
+            .unwrap_or("");
+        let version = &token[op.len()..];
+        match (pending_op.take(), op, version) {
+            (Some(_), _, _) if !op.is_empty() => {
+                return Err(format!("operator without a version in '{}'", alt))
+            }
+            (Some(pending), _, _) => comparators.push(comparator(pending, version)?),
+            (None, _, "") => pending_op = Some(op),
+            (None, _, _) => comparators.push(comparator(op, version)?),
         }
-        found_digit = true;
-        result.push(c);
     }
-
-    result
+    if pending_op.is_some() {
+        return Err(format!("operator without a version in '{}'", alt));
+    }
+    if comparators.is_empty() {
+        comparators.push("*".into());
+    }
+    Ok(comparators)
 }
 
-fn normalize_semver_string(s: &str) -> String {
-    // keep prefix characters (~,^,>,<,=) if present
-    let mut prefix = String::new();
-    let mut core_start = 0;
-    for (i, c) in s.char_indices() {
-        if c.is_ascii_digit() {
-            core_start = i;
-            break;
+/// One comparator for the `semver` crate. The operand is read like a published
+/// version, see [`ModVersion`], so `mc1.21.1-0.6.5-fabric` stands for 0.6.5.
+/// Wildcard parts are dropped and parts past the third truncated; a bare full
+/// version becomes `=`.
+fn comparator(op: &str, version: &str) -> Result<String, String> {
+    let not_a_version = || format!("'{}' is not a version", version);
+
+    let (mut parts, pre) = match version.split('.').position(is_wildcard) {
+        Some(wildcard) => {
+            let parts = version
+                .trim_start_matches('v')
+                .split('.')
+                .take(wildcard)
+                .map(|p| p.parse::<u64>().ok())
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(not_a_version)?;
+            (parts, Prerelease::EMPTY)
         }
-        prefix.push(c);
-    }
+        None => {
+            let parsed = ModVersion::parse(version, &[]).ok_or_else(not_a_version)?;
+            (parsed.release, parsed.pre)
+        }
+    };
+    parts.truncate(3);
+    let suffix = if pre.is_empty() {
+        String::new()
+    } else {
+        // Pre-releases need a full version
+        parts.resize(3, 0);
+        format!("-{}", pre)
+    };
+    let joined = parts
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(".");
+
+    Ok(match (op, parts.len()) {
+        (_, 0) => "*".into(),
+        ("", 3) => format!("={}{}", joined, suffix),
+        // A partial bare version matches every version under it
+        ("", _) => format!("{}.*", joined),
+        _ => format!("{}{}{}", op, joined, suffix),
+    })
+}
 
-    let core = &s[core_start..];
-    let mut parts: Vec<&str> = core.split('.').collect();
+fn is_wildcard(part: &str) -> bool {
+    matches!(part, "x" | "X" | "*")
+}
 
-    if parts.len() > 3 {
-        parts.truncate(3); // drop extra build digits
-    } else if parts.len() < 3 {
-        while parts.len() < 3 {
-            parts.push("0");
+/// Highest version matching `range` that is on `channel` or a more stable one.
+/// Versions are compared on the mod's own version, see [`ModVersion`], and
+/// by publish date when that is equal or can't be read.
+pub fn resolve_version(
+    range: &str,
+    available: &[VersionResult],
+    channel: Channel,
+) -> Option<VersionResult> {
+    let available: Vec<&VersionResult> = available
+        .iter()
+        .filter(|v| v.version_type <= channel)
+        .collect();
+    resolve_with_semver(range, &available, channel)
+        .or_else(|| resolve_any(range, &available))
+        .or_else(|| resolve_with_exact(range, &available))
+}
+
+/// Whether a published version is in `spec`, pre-releases counting when `channel` allows them
+pub fn satisfies(
+    spec: &VersionSpec,
+    version: &str,
+    minecraft_versions: &[String],
+    channel: Channel,
+) -> bool {
+    match spec {
+        VersionSpec::Exact(v) => v == version,
+        VersionSpec::Range(r) => {
+            let candidate = VersionResult {
+                version: version.to_string(),
+                minecraft_versions: minecraft_versions.to_vec(),
+                ..Default::default()
+            };
+            resolve_with_semver(r, &[&candidate], channel).is_some()
+                || resolve_with_exact(r, &[&candidate]).is_some()
         }
     }
-
-    format!("{}{}", prefix, parts.join("."))
 }
 
-/// Try resolving with raw semver
-fn resolve_with_semver(range: &str, available: &[VersionResult]) -> Option<VersionResult> {
-    let normalized = normalize_semver_string(range);
-    let req = VersionReq::parse(&normalized).ok()?;
-    let mut parsed: Vec<_> = available
+/// Highest version whose mod version matches the range
+fn resolve_with_semver(
+    range: &str,
+    available: &[&VersionResult],
+    channel: Channel,
+) -> Option<VersionResult> {
+    let req = VersionRange::parse(range).ok()?;
+    available
         .iter()
         .filter_map(|v| {
-            let fixed = normalize_semver_string(&v.version);
-            Version::parse(&fixed).ok().map(|sv| (sv, v.clone()))
+            let parsed = ModVersion::parse(&v.version, &v.minecraft_versions)?;
+            range_matches(&req, &parsed, channel).then_some((parsed, *v))
         })
-        .collect();
-    parsed.sort_by(|a, b| b.0.cmp(&a.0));
-    parsed
-        .into_iter()
-        .find(|(sv, _)| req.matches(sv))
-        .map(|(_, v)| v)
+        .max_by(|(a, va), (b, vb)| a.cmp(b).then_with(|| va.published.cmp(&vb.published)))
+        .map(|(_, v)| v.clone())
 }
 
-/// Try resolving with normalized semver
-fn resolve_with_normalized(range: &str, available: &[VersionResult]) -> Option<VersionResult> {
-    let r_norm = normalize_version(range);
+/// `semver` never matches a pre-release against a range without one, so on the
+/// beta and alpha channels `0.6.1-beta.2` is in `^0.6` by its release part
+fn range_matches(req: &VersionRange, version: &ModVersion, channel: Channel) -> bool {
+    let semver = version.to_semver();
+    if req.matches(&semver) {
+        return true;
+    }
+    channel > Channel::Release
+        && !semver.pre.is_empty()
+        && req.matches(&Version {
+            pre: Prerelease::EMPTY,
+            ..semver
+        })
+}
 
-    let available_norm: Vec<VersionResult> = available
+/// `*` takes the highest version, or the newest by publish date when
+/// some versions follow no scheme that can be read
+fn resolve_any(range: &str, available: &[&VersionResult]) -> Option<VersionResult> {
+    if range.trim() != "*" {
+        return None;
+    }
+    let parsed: Option<Vec<_>> = available
         .iter()
-        .map(|v| {
-            let mut clone = v.clone();
-            clone.version = normalize_version(&clone.version);
-            clone
-        })
+        .map(|v| ModVersion::parse(&v.version, &v.minecraft_versions).map(|p| (p, *v)))
         .collect();
-
-    resolve_with_semver(&r_norm, &available_norm)
+    match parsed {
+        Some(parsed) => parsed
+            .into_iter()
+            .max_by(|(a, va), (b, vb)| a.cmp(b).then_with(|| va.published.cmp(&vb.published)))
+            .map(|(_, v)| v.clone()),
+        None => available
+            .iter()
+            .max_by(|a, b| a.published.cmp(&b.published))
+            .map(|v| (*v).clone()),
+    }
 }
 
 /// Fallback: try exact match without range operators
-fn resolve_with_exact(range: &str, available: &[VersionResult]) -> Option<VersionResult> {
+fn resolve_with_exact(range: &str, available: &[&VersionResult]) -> Option<VersionResult> {
     let clean = range.trim_start_matches(|c: char| "^~><=*".contains(c));
-    available.iter().find(|v| v.version == clean).cloned()
+    available
+        .iter()
+        .find(|v| v.version == clean)
+        .map(|v| (*v).clone())
 }
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod tests {
+    use crate::app::{
+        helpers::semver::{is_semver_range, resolve_version, satisfies},
+        modules::{
+            manifest::models::{Channel, PartialManifest, VersionSpec},
+            repositories::models::VersionResult,
+        },
+    };
+
+    fn versions(list: &[(&str, Channel)]) -> Vec<VersionResult> {
+        list.iter()
+            .map(|(version, channel)| VersionResult {
+                version: version.to_string(),
+                version_type: *channel,
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    fn resolve(range: &str, available: &[VersionResult], channel: Channel) -> Option<String> {
+        resolve_version(range, available, channel).map(|v| v.version)
+    }
+
+    #[test]
+    fn ranges_resolve_on_the_allowed_channel() {
+        let available = versions(&[
+            ("1.3.0-alpha.1", Channel::Alpha),
+            ("1.2.1-beta.2", Channel::Beta),
+            ("1.2.0", Channel::Release),
+            ("1.1.0", Channel::Release),
+        ]);
+
+        assert_eq!(
+            resolve("^1.0", &available, Channel::Release).as_deref(),
+            Some("1.2.0")
+        );
+        assert_eq!(
+            resolve("^1.0", &available, Channel::Beta).as_deref(),
+            Some("1.2.1-beta.2")
+        );
+        assert_eq!(
+            resolve("*", &available, Channel::Beta).as_deref(),
+            Some("1.2.1-beta.2")
+        );
+        assert_eq!(
+            resolve("^1.0", &available, Channel::Alpha).as_deref(),
+            Some("1.3.0-alpha.1")
+        );
+        assert_eq!(resolve("^1.3", &available, Channel::Release), None);
+
+        // Only betas published for the range
+        let betas = versions(&[
+            ("0.6.1-beta.2", Channel::Beta),
+            ("0.6.1-beta.1", Channel::Beta),
+        ]);
+        assert_eq!(
+            resolve("^0.6", &betas, Channel::Beta).as_deref(),
+            Some("0.6.1-beta.2")
+        );
+        assert_eq!(resolve("^0.6", &betas, Channel::Release), None);
+    }
+
+    #[test]
+    fn locked_pre_releases_satisfy_their_range_on_their_channel() {
+        let range = VersionSpec::Range("^0.6".into());
+
+        assert!(satisfies(&range, "0.6.1-beta.2", &[], Channel::Beta));
+        assert!(satisfies(&range, "0.6.1-alpha.3", &[], Channel::Alpha));
+        assert!(!satisfies(&range, "0.6.1-beta.2", &[], Channel::Release));
+        assert!(!satisfies(&range, "0.7.0-beta.1", &[], Channel::Beta));
+        assert!(satisfies(
+            &range,
+            "mc1.21.1-0.6.1-beta.2-fabric",
+            &["1.21.1".to_string()],
+            Channel::Beta
+        ));
+    }
+
+    #[test]
+    fn npm_style_ranges() {
+        let available = versions(&[
+            ("2.0.0", Channel::Release),
+            ("1.6.1", Channel::Release),
+            ("1.5.0", Channel::Release),
+            ("1.4.2", Channel::Release),
+            ("1.3.0", Channel::Release),
+            ("0.9.0", Channel::Release),
+        ]);
+        let best = |range: &str| resolve(range, &available, Channel::Release);
+
+        assert_eq!(best("^0.5 || ^1.3").as_deref(), Some("1.6.1"));
+        assert_eq!(best("<1.0 || >=3").as_deref(), Some("0.9.0"));
+        assert_eq!(best("1.2 - 1.4").as_deref(), Some("1.4.2"));
+        assert_eq!(best("1.2.0 - 1.4.0").as_deref(), Some("1.3.0"));
+        assert_eq!(best("1.5.x").as_deref(), Some("1.5.0"));
+        assert_eq!(best("1.x").as_deref(), Some("1.6.1"));
+        assert_eq!(best(">= 1.3, < 1.5").as_deref(), Some("1.4.2"));
+        assert_eq!(best("=1.3.0").as_deref(), Some("1.3.0"));
+        assert_eq!(best("=1.3").as_deref(), Some("1.3.0"));
+    }
+
+    #[test]
+    fn ranges_match_mod_versions_with_minecraft_parts() {
+        let mc = vec!["1.21.1".to_string()];
+        let range = |r: &str| VersionSpec::Range(r.into());
+
+        assert!(satisfies(
+            &range("=0.6.5"),
+            "mc1.21.1-0.6.5-fabric",
+            &mc,
+            Channel::Release
+        ));
+        assert!(satisfies(
+            &range("0.5.x || 0.6.x"),
+            "0.6.5+1.21.1",
+            &mc,
+            Channel::Release
+        ));
+        assert!(satisfies(
+            &range("0.6 - 0.7"),
+            "1.21.1-0.6.5",
+            &mc,
+            Channel::Release
+        ));
+        assert!(!satisfies(
+            &range("0.6.0 - 0.6.4"),
+            "1.21.1-0.6.5",
+            &mc,
+            Channel::Release
+        ));
+    }
+
+    #[test]
+    fn range_specs_are_recognized_and_validated() {
+        for spec in ["^1", "=1.2.3", "1.20.x", "1.2 - 1.4", "1.0 || 2.0", "*"] {
+            assert!(is_semver_range(spec), "{spec}");
+        }
+        for spec in ["1.2.3", "0.5.8+1.20.1", "mc1.21.1-0.6.5", "x-mod"] {
+            assert!(!is_semver_range(spec), "{spec}");
+        }
+
+        let spec = |s: &str| serde_json::from_value::<VersionSpec>(serde_json::json!(s));
+        assert!(matches!(spec("1.2 - 1.4"), Ok(VersionSpec::Range(_))));
+        assert!(matches!(spec("1.2.3"), Ok(VersionSpec::Exact(_))));
+        let err = spec(">=1.2 || ^abc").unwrap_err().to_string();
+        assert!(err.contains("'abc' is not a version"), "{err}");
+    }
+
+    #[test]
+    fn ranges_over_published_versions_load() {
+        let manifest = PartialManifest::from_value(serde_json::json!({
+            "mods": {
+                "modrinth:sodium": "^mc1.21.1-0.6.5-fabric",
+                "modrinth:lithium": "^fabric-0.6.5",
+                "modrinth:iris": { "version": ">=1.8.0+1.21.1 || 1.7.x" }
+            }
+        }))
+        .unwrap();
+        let mods = manifest.mods.unwrap();
+        let mc = vec!["1.21.1".to_string()];
+
+        let sodium = &mods["modrinth:sodium"].version;
+        assert!(matches!(sodium, VersionSpec::Range(_)));
+        assert!(satisfies(
+            sodium,
+            "mc1.21.1-0.6.9-fabric",
+            &mc,
+            Channel::Release
+        ));
+        assert!(!satisfies(
+            sodium,
+            "mc1.21.1-0.7.0-fabric",
+            &mc,
+            Channel::Release
+        ));
+        assert!(satisfies(
+            &mods["modrinth:lithium"].version,
+            "fabric-0.6.7",
+            &mc,
+            Channel::Release
+        ));
+        assert!(satisfies(
+            &mods["modrinth:iris"].version,
+            "1.8.1+1.21.1",
+            &mc,
+            Channel::Release
+        ));
+    }
+}
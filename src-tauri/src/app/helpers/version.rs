@@ -0,0 +1,183 @@
+use semver::{BuildMetadata, Prerelease, Version};
+use std::cmp::Ordering;
+
+const LOADERS: [&str; 4] = ["fabric", "forge", "neoforge", "quilt"];
+const PRE_RELEASE_TAGS: [&str; 6] = ["alpha", "beta", "pre", "rc", "snapshot", "dev"];
+
+/// A mod version as published, with the Minecraft version and loader that are
+/// often embedded in it taken apart, e.g. `mc1.21.1-0.6.5-fabric`, `0.5.8+1.20.1`
+/// or `1.21.1-2.3.4+build.5` are all mod versions 0.6.5, 0.5.8 and 2.3.4.
+/// Versions compare on the mod's own version only, see the [`Ord`] impl.
+#[derive(Debug, Clone)]
+pub struct ModVersion {
+    /// Numeric components of the mod's own version, e.g. `[0, 6, 5]`
+    pub release: Vec<u64>,
+    /// Pre-release tag such as `beta.2`, empty for releases
+    pub pre: Prerelease,
+    pub minecraft: Option<String>,
+    pub loader: Option<String>,
+}
+
+/// A piece of a version string between `-`, `+`, `_` and spaces
+struct Token<'a> {
+    text: &'a str,
+    after_plus: bool,
+}
+
+struct Number<'a> {
+    text: &'a str,
+    parts: Vec<u64>,
+    mc_prefixed: bool,
+    after_plus: bool,
+}
+
+impl ModVersion {
+    /// Parse a published version. `minecraft_versions`, the game versions the
+    /// provider lists for it, tell embedded Minecraft versions from the mod's own.
+    /// `None` if there is no numeric version at all.
+    pub fn parse(raw: &str, minecraft_versions: &[String]) -> Option<Self> {
+        let lower = raw.trim().to_lowercase();
+
+        let mut numbers = vec![];
+        let mut pre = None;
+        let mut loader = None;
+        for token in tokenize(&lower) {
+            if let Some(n) = number(&token) {
+                numbers.push(n);
+            } else if let Some(l) = LOADERS.iter().find(|l| **l == token.text) {
+                loader.get_or_insert(l.to_string());
+            } else if let Some(p) = pre_release(token.text) {
+                pre.get_or_insert(p);
+            }
+        }
+
+        let minecraft = if numbers.len() > 1 {
+            minecraft_index(&numbers, minecraft_versions)
+        } else {
+            None
+        };
+        let own = (0..numbers.len()).find(|i| Some(*i) != minecraft)?;
+
+        Some(Self {
+            release: numbers[own].parts.clone(),
+            pre: pre.unwrap_or(Prerelease::EMPTY),
+            minecraft: minecraft.map(|i| numbers[i].text.to_string()),
+            loader,
+        })
+    }
+
+    /// The mod version as semver, for matching against ranges.
+    /// Components past the third only count when ordering.
+    pub fn to_semver(&self) -> Version {
+        let part = |i: usize| self.release.get(i).copied().unwrap_or(0);
+        Version {
+            major: part(0),
+            minor: part(1),
+            patch: part(2),
+            pre: self.pre.clone(),
+            build: BuildMetadata::EMPTY,
+        }
+    }
+}
+
+impl Ord for ModVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        let len = self.release.len().max(other.release.len());
+        let part = |v: &Self, i: usize| v.release.get(i).copied().unwrap_or(0);
+        (0..len)
+            .map(|i| part(self, i).cmp(&part(other, i)))
+            .find(|o| o.is_ne())
+            .unwrap_or(Ordering::Equal)
+            // A release is newer than its pre-releases
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl PartialEq for ModVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for ModVersion {}
+
+impl PartialOrd for ModVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn tokenize(s: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut after_plus = false;
+    let mut start = 0;
+    for (i, c) in s.char_indices().chain([(s.len(), '-')]) {
+        if matches!(c, '-' | '+' | '_' | ' ') {
+            if i > start {
+                tokens.push(Token {
+                    text: &s[start..i],
+                    after_plus,
+                });
+            }
+            after_plus |= c == '+';
+            start = i + c.len_utf8();
+        }
+    }
+    tokens
+}
+
+/// `1.2.3`, `v1.2.3` or `mc1.21.1`
+fn number<'a>(token: &Token<'a>) -> Option<Number<'a>> {
+    let (text, mc_prefixed) = match token.text.strip_prefix("mc") {
+        Some(rest) => (rest, true),
+        None => (token.text.strip_prefix('v').unwrap_or(token.text), false),
+    };
+    let parts = text
+        .split('.')
+        .map(|p| p.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    Some(Number {
+        text,
+        parts,
+        mc_prefixed,
+        after_plus: token.after_plus,
+    })
+}
+
+/// `beta.2`, `beta2` or `rc1` as a semver pre-release
+fn pre_release(text: &str) -> Option<Prerelease> {
+    let tag = PRE_RELEASE_TAGS.iter().find(|t| text.starts_with(**t))?;
+    let rest = text[tag.len()..].trim_start_matches('.');
+    let normalized = if rest.is_empty() {
+        tag.to_string()
+    } else {
+        format!("{}.{}", tag, rest)
+    };
+    Prerelease::new(&normalized).ok()
+}
+
+/// Which of several numbers is the Minecraft version: one the provider lists,
+/// one marked `mc`, or else one shaped like a Minecraft version, preferring
+/// build metadata (`0.5.8+1.20.1`) over the front (`1.20.1-0.5.8`).
+fn minecraft_index(numbers: &[Number], minecraft_versions: &[String]) -> Option<usize> {
+    let position = |f: &dyn Fn(&Number) -> bool| numbers.iter().position(f);
+
+    position(&|n| minecraft_versions.iter().any(|v| v == n.text))
+        .or_else(|| position(&|n| n.mc_prefixed))
+        .or_else(|| position(&|n| n.after_plus && looks_like_minecraft(n)))
+        .or_else(|| position(&|n| looks_like_minecraft(n)))
+}
+
+/// `1.7` to `1.21.x`, or the year based `26.1` scheme
+fn looks_like_minecraft(n: &Number) -> bool {
+    match n.parts[..] {
+        [1, minor] | [1, minor, _] => (7..=21).contains(&minor),
+        [year, _] | [year, _, _] => (25..=99).contains(&year),
+        _ => false,
+    }
+}
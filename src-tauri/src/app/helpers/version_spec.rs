@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use crate::app::{
+        helpers::{semver::resolve_version, version::ModVersion},
+        modules::{manifest::models::Channel, repositories::models::VersionResult},
+    };
+
+    fn parse(raw: &str) -> ModVersion {
+        ModVersion::parse(raw, &[]).unwrap()
+    }
+
+    #[test]
+    fn embedded_minecraft_versions_and_loaders_are_taken_apart() {
+        let v = parse("mc1.21.1-0.6.5-fabric");
+        assert_eq!(v.release, [0, 6, 5]);
+        assert_eq!(v.minecraft.as_deref(), Some("1.21.1"));
+        assert_eq!(v.loader.as_deref(), Some("fabric"));
+
+        let v = parse("1.21.1-2.3.4+build.5");
+        assert_eq!(v.release, [2, 3, 4]);
+        assert_eq!(v.minecraft.as_deref(), Some("1.21.1"));
+
+        let v = parse("0.5.8+1.20.1");
+        assert_eq!(v.release, [0, 5, 8]);
+        assert_eq!(v.minecraft.as_deref(), Some("1.20.1"));
+
+        let v = parse("sodium-fabric-0.6.0-beta.2+mc1.21");
+        assert_eq!(v.release, [0, 6, 0]);
+        assert_eq!(v.pre.as_str(), "beta.2");
+        assert_eq!(v.minecraft.as_deref(), Some("1.21"));
+
+        // The provider's game versions settle what the shape can't
+        let v = ModVersion::parse("1.2.0-1.12.2", &["1.12.2".into()]).unwrap();
+        assert_eq!(v.release, [1, 2, 0]);
+
+        // A lone number is the mod's version, whatever it looks like
+        assert_eq!(parse("1.20.1").release, [1, 20, 1]);
+        assert!(ModVersion::parse("latest-build", &[]).is_none());
+    }
+
+    #[test]
+    fn versions_order_on_every_component() {
+        assert!(parse("1.2.3.10") > parse("1.2.3.9"));
+        assert!(parse("0.6.0") > parse("0.6.0-beta.2"));
+        assert!(parse("0.6.0-rc1") > parse("0.6.0-beta.2"));
+        assert!(parse("mc1.21-0.6.10") > parse("mc1.21-0.6.9"));
+
+        // Equal when they order equal, whatever they were built for
+        assert_eq!(parse("1.2"), parse("1.2.0"));
+        assert_eq!(parse("mc1.21-0.6.0-fabric"), parse("0.6.0+1.20.1"));
+        assert_ne!(parse("0.6.0"), parse("0.6.0-beta.2"));
+    }
+
+    fn published(version: &str, day: u32) -> VersionResult {
+        VersionResult {
+            version: version.into(),
+            minecraft_versions: vec!["1.21.1".into()],
+            published: Some(Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn resolution_compares_mod_versions_and_falls_back_to_dates() {
+        let available = [
+            published("1.21.1-0.6.9", 3),
+            published("1.21.1-0.6.10", 2),
+            published("1.21.1-0.5.12", 1),
+        ];
+        let resolved = resolve_version("^0.6", &available, Channel::Release).unwrap();
+        assert_eq!(resolved.version, "1.21.1-0.6.10");
+
+        let resolved = resolve_version("*", &available, Channel::Release).unwrap();
+        assert_eq!(resolved.version, "1.21.1-0.6.10");
+
+        // No scheme to compare: the newest upload wins
+        let unnamed = [published("build-b", 1), published("build-a", 5)];
+        let resolved = resolve_version("*", &unnamed, Channel::Release).unwrap();
+        assert_eq!(resolved.version, "build-a");
+    }
+}
@@ -1,19 +1,37 @@
 pub mod commands;
 mod config;
+mod error;
+#[cfg(feature = "gui")]
+pub mod gui;
 pub mod helpers;
 pub mod modules;
 
+use once_cell::sync::OnceCell;
+use std::{
+    env,
+    io::{stdin, stdout, IsTerminal},
+};
+
 pub use config::Config;
+pub use error::McpmError;
 
-pub static mut INTERACTIVE: bool = false;
+static INTERACTIVE: OnceCell<bool> = OnceCell::new();
 pub const PAGINATION_SIZE: usize = 20;
 
-// Safe getter
+/// Whether prompts and pickers may ask the user anything.
+/// False until [`set_interactive`] is called.
 pub fn is_interactive() -> bool {
-    unsafe { INTERACTIVE }
+    INTERACTIVE.get().copied().unwrap_or(false)
+}
+
+/// Set once at startup, later calls are ignored
+pub fn set_interactive(value: bool) {
+    INTERACTIVE.set(value).ok();
 }
 
-// Unsafe setter (set once at startup)
-pub unsafe fn set_interactive(value: bool) {
-    INTERACTIVE = value;
+/// The single interactivity decision: only ask when not disabled by a flag,
+/// not running in CI and both stdin and stdout are terminals.
+pub fn detect_interactive(disabled: bool) -> bool {
+    let ci = env::var("CI").is_ok_and(|v| !matches!(v.as_str(), "" | "0" | "false"));
+    !disabled && !ci && stdin().is_terminal() && stdout().is_terminal()
 }
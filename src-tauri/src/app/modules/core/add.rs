@@ -1,14 +1,23 @@
+use std::sync::Mutex;
+
 use crate::app::{
     helpers::{
         as_str,
-        cli::{pick, pick_with_pagination},
-        semver::is_semver_range,
+        semver::{is_semver_range, resolve_version, VersionRange},
+        version::ModVersion,
     },
     modules::{
         core::ops::manager::ModManager,
-        manifest::models::{ModEntry, Provider, VersionSpec},
-        repositories::{models::VersionResult, modrinth::ModrinthRepository, RepositoryService},
+        io::{traits::PromptResult, use_io},
+        manifest::models::{Channel, ModEntry, Provider, VersionSpec},
+        minecraft::MinecraftService,
+        repositories::{
+            models::{ModResult, VersionResult},
+            modrinth::ModrinthRepository,
+            RepositoryService,
+        },
     },
+    McpmError,
 };
 
 pub struct Add;
@@ -20,31 +29,24 @@ impl Add {
         provider: Option<Provider>,
         exact: bool,
         search: bool,
-    ) -> Result<(), String> {
-        let mut manager = ModManager::load()
-            .await
-            .map_err(|e| format!("Failed to initialize ModManager: {}", e))?;
+        channel: Option<Channel>,
+    ) -> Result<ModEntry, McpmError> {
+        let mut manager = ModManager::load().await?;
+        MinecraftService::validate(&manager.manifest.minecraft_version, true).await?;
 
         let provider = provider.unwrap_or(manager.manifest.default_provider.clone());
         let repo_service =
             RepositoryService::new().with_provider("modrinth", Box::new(ModrinthRepository::new()));
 
         let project = if search {
-            pick_with_pagination(
-                |page| {
-                    let service_ref = &repo_service;
-                    async move { service_ref.search(&id, page).await }
-                },
-                "Select a project",
-                |m| format!("[{}] {} ({})", m.source, m.name, m.url),
-            )
-            .await
-            .ok_or_else(|| format!("No mod found for '{}'", id))?
+            search_project(&repo_service, id)
+                .await?
+                .ok_or_else(|| McpmError::NotFound(format!("Mod '{}'", id)))?
         } else {
             repo_service
-                .find(&id)
-                .await
-                .ok_or_else(|| format!("No mod found for '{}'", id))?
+                .find(&format!("{}:{}", provider, id))
+                .await?
+                .ok_or_else(|| McpmError::NotFound(format!("Mod '{}'", id)))?
         };
 
         let versions = repo_service
@@ -53,13 +55,26 @@ impl Add {
                 &[manager.manifest.minecraft_version.clone()],
                 &[as_str(&manager.manifest.modloader)],
             )
-            .await;
+            .await?;
 
         if versions.is_empty() {
-            return Err(format!(
-                "No compatible versions found for '{}' with Minecraft {} + {:?}",
-                project.name, manager.manifest.minecraft_version, manager.manifest.modloader
-            ));
+            return Err(McpmError::NoCompatibleVersion {
+                slug: project.slug.clone(),
+                detail: format!(
+                    "Minecraft {} + {:?}",
+                    manager.manifest.minecraft_version, manager.manifest.modloader
+                ),
+            });
+        }
+
+        // Re-adding an existing mod keeps its options
+        let mut options = manager
+            .manifest
+            .get_mod(&provider, &project.slug)
+            .map(|spec| spec.options.clone())
+            .unwrap_or_default();
+        if channel.is_some() {
+            options.channel = channel;
         }
 
         let chosen = pick_version(
@@ -67,62 +82,152 @@ impl Add {
             &versions,
             &project.name,
             &manager.manifest.minecraft_version,
+            options.channel.unwrap_or(manager.manifest.channel),
         )
-        .ok_or_else(|| "Version selection cancelled".to_string())?;
+        .await?
+        .ok_or(McpmError::Cancelled)?;
 
-        let version_spec = resolve_version_spec(&version, &chosen.version, exact);
+        let version_spec = resolve_version_spec(&version, &chosen, exact);
 
         let entry = ModEntry {
             slug: project.slug.clone(),
+            id: Some(project.id.clone()),
             version: version_spec,
             provider: provider.clone(),
+            options,
         };
 
         manager.manifest.insert_mod_entry(&entry);
 
-        manager
-            .refresh_mod(&entry, Some(&versions), false)
-            .await
-            .map_err(|e| format!("Failed to update lockfile: {}", e))?;
+        manager.refresh_mod(&entry, Some(&versions), false).await?;
 
-        manager
-            .save_all()
-            .map_err(|e| format!("Failed to save lock file: {}", e))?;
-        Ok(())
+        manager.save_all()?;
+        Ok(entry)
     }
 }
 
-fn resolve_version_spec(requested: &Option<String>, chosen: &str, exact: bool) -> VersionSpec {
+pub(crate) fn resolve_version_spec(
+    requested: &Option<String>,
+    chosen: &VersionResult,
+    exact: bool,
+) -> VersionSpec {
     match requested {
         Some(v) if is_semver_range(v) => VersionSpec::Range(v.clone()),
-        Some(_) if exact => VersionSpec::Exact(chosen.to_string()),
-        Some(_) => VersionSpec::Range(format!("^{}", chosen)),
-        None if exact => VersionSpec::Exact(chosen.to_string()),
-        None => VersionSpec::Range(format!("^{}", chosen)),
+        _ if exact => VersionSpec::Exact(chosen.version.clone()),
+        _ => caret(chosen),
+    }
+}
+
+/// `^` on the mod's own version, the one ranges are matched against,
+/// so `1.21.1-2.3.4+build.5` gives `^2.3.4`
+fn caret(chosen: &VersionResult) -> VersionSpec {
+    match ModVersion::parse(&chosen.version, &chosen.minecraft_versions) {
+        Some(parsed) => VersionSpec::Range(format!("^{}", parsed.to_semver())),
+        None => VersionSpec::Exact(chosen.version.clone()),
     }
 }
 
-fn pick_version(
+/// Let the user pick a search result, loading more pages on request
+async fn search_project(
+    repo_service: &RepositoryService,
+    query: &str,
+) -> Result<Option<ModResult>, McpmError> {
+    let found = Mutex::new(Vec::<ModResult>::new());
+
+    let selection = use_io()
+        .select_paginated(
+            "Select a project",
+            Box::new(|page| {
+                let found = &found;
+                Box::pin(async move {
+                    let results = repo_service.search(query, page).await?;
+                    let labels = results
+                        .iter()
+                        .map(|m| format!("[{}] {} ({})", m.source, m.name, m.url))
+                        .collect();
+                    found.lock().unwrap().extend(results);
+                    Ok(labels)
+                })
+            }),
+        )
+        .await?;
+
+    let mut found = found.into_inner().unwrap();
+    Ok(match selection {
+        PromptResult::Response(i) => Some(found.swap_remove(i)),
+        PromptResult::Cancel => None,
+    })
+}
+
+/// The requested version, or the newest one on `channel` or a more stable one,
+/// ordered like the resolver does. An explicitly requested version is taken whatever its channel.
+pub(crate) async fn pick_version(
     requested: &Option<String>,
     versions: &[VersionResult],
     project_name: &str,
     minecraft_version: &str,
-) -> Option<VersionResult> {
-    match requested {
-        Some(r) => versions
-            .iter()
-            .find(|v| &v.version == r)
-            .cloned()
-            .or_else(|| {
-                pick(
-                    versions,
-                    &format!(
-                        "Requested version '{}' not found for '{}' with Minecraft '{}'. Pick a compatible version",
-                        r, project_name, minecraft_version
-                    ),
-                    |m| format!("{} [{}]", m.version, m.minecraft_versions.join(", ")),
-                )
-            }),
-        None => versions.first().cloned(),
+    channel: Channel,
+) -> Result<Option<VersionResult>, McpmError> {
+    let Some(r) = requested else {
+        if let Some(newest) = resolve_version("*", versions, channel) {
+            return Ok(Some(newest));
+        }
+        let newest = resolve_version("*", versions, Channel::Alpha)
+            .expect("projects without versions are rejected before picking one");
+        return Err(McpmError::NoCompatibleVersion {
+            slug: project_name.to_string(),
+            detail: format!(
+                "no {} versions for Minecraft {}, newest is {} ({}), allow it with --channel {}",
+                channel,
+                minecraft_version,
+                newest.version,
+                newest.version_type,
+                newest.version_type
+            ),
+        });
+    };
+
+    if let Some(v) = versions.iter().find(|v| &v.version == r) {
+        return Ok(Some(v.clone()));
     }
+    if is_semver_range(r) {
+        VersionRange::parse(r).map_err(McpmError::InvalidInput)?;
+        if let Some(v) = resolve_version(r, versions, channel) {
+            return Ok(Some(v));
+        }
+    }
+
+    // Substituting another version is only done when the user picks it
+    let io = use_io();
+    if !io.can_prompt() {
+        return Err(McpmError::NoCompatibleVersion {
+            slug: project_name.to_string(),
+            detail: format!(
+                "version '{}' not found for Minecraft {}",
+                r, minecraft_version
+            ),
+        });
+    }
+
+    let labels: Vec<String> = versions
+        .iter()
+        .map(|m| match m.version_type {
+            Channel::Release => format!("{} [{}]", m.version, m.minecraft_versions.join(", ")),
+            c => format!(
+                "{} ({}) [{}]",
+                m.version,
+                c,
+                m.minecraft_versions.join(", ")
+            ),
+        })
+        .collect();
+    let question = format!(
+        "Requested version '{}' not found for '{}' with Minecraft '{}'. Pick a compatible version",
+        r, project_name, minecraft_version
+    );
+
+    Ok(match io.select(&question, &labels, 0).await? {
+        PromptResult::Response(i) => Some(versions[i].clone()),
+        PromptResult::Cancel => None,
+    })
 }
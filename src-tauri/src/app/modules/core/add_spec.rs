@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use crate::app::modules::{
+        core::add::{pick_version, resolve_version_spec},
+        lock::{models::LockFile, services::LockService},
+        manifest::models::{Channel, Manifest, ModEntry, Provider, VersionSpec},
+        repositories::{models::VersionResult, RepositoryService},
+    };
+
+    fn version(version: &str) -> VersionResult {
+        VersionResult {
+            mod_id: "P7dR8mSH".into(),
+            version: version.into(),
+            minecraft_versions: vec!["1.21.1".into()],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn added_mods_upgrade_to_later_builds() {
+        let added = version("1.21.1-2.3.4+build.5");
+        let spec = resolve_version_spec(&None, &added, false);
+        assert!(matches!(&spec, VersionSpec::Range(r) if r == "^2.3.4"));
+
+        let entry = ModEntry {
+            slug: "fabric-api".into(),
+            id: Some("P7dR8mSH".into()),
+            version: spec,
+            provider: Provider::Modrinth,
+            options: Default::default(),
+        };
+        let manifest = Manifest {
+            minecraft_version: "1.21.1".into(),
+            ..Default::default()
+        };
+        let repo_service = RepositoryService::new();
+        let mut lock_service = LockService {
+            lock: LockFile::default(),
+        };
+
+        let available = [added.clone()];
+        lock_service
+            .update_entry(&entry, &manifest, &repo_service, Some(&available), false)
+            .await
+            .unwrap();
+        assert_eq!(
+            lock_service.get_version(&entry).as_deref(),
+            Some("1.21.1-2.3.4+build.5")
+        );
+        // Only an upgrade resolves it again, so only that checks Minecraft
+        assert!(!lock_service.needs_update(&entry, &manifest, false));
+        assert!(lock_service.needs_update(&entry, &manifest, true));
+
+        let available = [
+            version("1.21.1-3.0.0+build.9"),
+            version("1.21.1-2.3.5+build.6"),
+            added,
+        ];
+        lock_service
+            .update_entry(&entry, &manifest, &repo_service, Some(&available), true)
+            .await
+            .unwrap();
+        assert_eq!(
+            lock_service.get_version(&entry).as_deref(),
+            Some("1.21.1-2.3.5+build.6")
+        );
+    }
+
+    #[tokio::test]
+    async fn adding_picks_the_highest_version_not_the_first_listed() {
+        let beta = VersionResult {
+            version_type: Channel::Beta,
+            ..version("1.21.1-2.4.0-beta.1")
+        };
+        let available = [
+            version("1.21.1-2.3.4+build.5"),
+            beta,
+            version("1.21.1-2.3.10+build.7"),
+        ];
+        let pick = |channel| pick_version(&None, &available, "Fabric API", "1.21.1", channel);
+
+        let picked = pick(Channel::Release).await.unwrap().unwrap();
+        assert_eq!(picked.version, "1.21.1-2.3.10+build.7");
+        let picked = pick(Channel::Beta).await.unwrap().unwrap();
+        assert_eq!(picked.version, "1.21.1-2.4.0-beta.1");
+    }
+}
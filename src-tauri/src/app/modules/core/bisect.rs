@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs, path::PathBuf};
+
+use crate::app::{
+    modules::{
+        core::{install::Install, ops::manager::ModManager},
+        lock::models::LockFile,
+        manifest::models::ModEntry,
+    },
+    Config, McpmError,
+};
+
+const BISECT_FILE: &str = ".mcpm/bisect.json";
+
+/// An ongoing bisect session, kept on disk between `mcpm bisect` calls
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BisectState {
+    pub suspects: Vec<String>,
+    pub testing: Vec<String>, // suspects disabled in the current step
+    pub step: usize,
+}
+
+impl BisectState {
+    pub fn new(suspects: Vec<String>) -> Self {
+        let mut state = Self {
+            suspects,
+            testing: vec![],
+            step: 0,
+        };
+        state.next_step();
+        state
+    }
+
+    /// Record whether the game works with `testing` disabled.
+    /// Returns the culprit once a single suspect is left.
+    pub fn mark(&mut self, works: bool) -> Option<String> {
+        self.suspects = if works {
+            self.testing.clone()
+        } else {
+            self.suspects
+                .iter()
+                .filter(|s| !self.testing.contains(s))
+                .cloned()
+                .collect()
+        };
+
+        if self.suspects.len() <= 1 {
+            return self.suspects.first().cloned();
+        }
+
+        self.next_step();
+        None
+    }
+
+    /// Remaining answers needed in the worst case
+    pub fn steps_left(&self) -> usize {
+        self.suspects.len().next_power_of_two().trailing_zeros() as usize
+    }
+
+    fn next_step(&mut self) {
+        self.step += 1;
+        self.testing = self.suspects[..self.suspects.len() / 2].to_vec();
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BisectOutcome {
+    Next(BisectState),
+    Found { culprit: String },
+    Reset,
+}
+
+pub struct Bisect;
+
+impl Bisect {
+    /// Start a session and install the first half of the suspects as disabled
+    pub async fn start() -> Result<BisectState, McpmError> {
+        if Self::path().exists() {
+            return Err(McpmError::Conflict(
+                "A bisect session is already running. Use `mcpm bisect reset` to end it".into(),
+            ));
+        }
+
+        let manager = ModManager::load().await?;
+
+        // Optional mods the player left out are not installed, so they can't be suspects
+        let selected = Install::pick(&manager.manifest, &Install::load_selection()?, &[])?;
+        let installed: Vec<ModEntry> = manager
+            .manifest
+            .mods_as_entries()
+            .into_iter()
+            .filter(|m| !m.options.optional || selected.contains(&m.to_key()))
+            .collect();
+        let suspects = Self::candidates(&installed, &manager.lock_service.lock);
+        if suspects.len() < 2 {
+            return Err(McpmError::InvalidInput(
+                "Bisecting needs at least two enabled, locked non-library mods".into(),
+            ));
+        }
+
+        let state = BisectState::new(suspects);
+        Self::apply(&state).await?;
+        Self::save(&state)?;
+
+        Ok(state)
+    }
+
+    /// Answer the current step, then either install the next one or
+    /// restore the original state once the culprit is known.
+    pub async fn mark(works: bool) -> Result<BisectOutcome, McpmError> {
+        let mut state = Self::load()?;
+
+        match state.mark(works) {
+            Some(culprit) => {
+                Self::reset().await?;
+                Ok(BisectOutcome::Found { culprit })
+            }
+            None => {
+                Self::apply(&state).await?;
+                Self::save(&state)?;
+                Ok(BisectOutcome::Next(state))
+            }
+        }
+    }
+
+    /// End the session and install the manifest's own state again
+    pub async fn reset() -> Result<(), McpmError> {
+        Self::apply_disabled(HashSet::new()).await?;
+
+        let path = Self::path();
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| McpmError::io("Failed to end bisect session", e))?;
+        }
+        Ok(())
+    }
+
+    /// Enabled, locked mods that no other locked mod requires
+    pub fn candidates(entries: &[ModEntry], lock: &LockFile) -> Vec<String> {
+        let required: HashSet<&str> = lock
+            .mods
+            .values()
+            .flat_map(|e| e.dependencies.iter().map(String::as_str))
+            .collect();
+
+        let mut keys: Vec<String> = entries
+            .iter()
+            .filter(|m| !m.options.disabled)
+            .map(|m| m.to_key())
+            .filter(|k| {
+                lock.mods
+                    .get(k)
+                    .is_some_and(|e| !required.contains(e.id.as_str()))
+            })
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    async fn apply(state: &BisectState) -> Result<(), McpmError> {
+        Self::apply_disabled(state.testing.iter().cloned().collect()).await
+    }
+
+    /// Install the current lock with `extra` disabled on top of the manifest
+    async fn apply_disabled(extra: HashSet<String>) -> Result<(), McpmError> {
+        let manager = ModManager::load().await?;
+
+        // Optional mods as the last install picked them
+        let selected = Install::pick(&manager.manifest, &Install::load_selection()?, &[])?;
+        let mut placements = Install::placements(&manager.manifest, &selected)?;
+        for key in extra {
+            placements.entry(key).or_default().disabled = true;
+        }
+
+        let plan = Install::plan_locked(&placements)?;
+        Install::apply(&plan).await
+    }
+
+    fn path() -> PathBuf {
+        Config::get().project_dir.join(BISECT_FILE)
+    }
+
+    fn load() -> Result<BisectState, McpmError> {
+        let content = fs::read_to_string(Self::path()).map_err(|_| {
+            McpmError::NotFound("Bisect session (start one with `mcpm bisect start`)".into())
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| McpmError::InvalidInput(format!("Corrupt bisect state: {}", e)))
+    }
+
+    fn save(state: &BisectState) -> Result<(), McpmError> {
+        let path = Self::path();
+        let save_error = |e| McpmError::io("Failed to save bisect state", e);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(save_error)?;
+        }
+        let json = serde_json::to_string_pretty(state).expect("bisect state serializes");
+        fs::write(path, json).map_err(save_error)
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::app::modules::{
+        core::bisect::{Bisect, BisectState},
+        lock::models::{LockEntry, LockFile},
+        manifest::models::{ModEntry, ModOptions, Provider, VersionSpec},
+    };
+
+    // Libraries (required by another locked mod) and disabled mods are never suspects,
+    // and each answer halves the suspects until the culprit is left.
+
+    #[test]
+    fn candidates_skip_libraries_and_disabled_mods() {
+        let lock = LockFile {
+            mods: HashMap::from([
+                lock_entry("modrinth:sodium", "sodium-id", &[]),
+                lock_entry("modrinth:indium", "indium-id", &["sodium-id"]),
+                lock_entry("modrinth:lithium", "lithium-id", &[]),
+                lock_entry("modrinth:broken", "broken-id", &[]),
+            ]),
+            ..Default::default()
+        };
+        let entries = vec![
+            mod_entry("sodium", false),
+            mod_entry("indium", false),
+            mod_entry("lithium", false),
+            mod_entry("broken", true),
+            mod_entry("unlocked", false),
+        ];
+
+        assert_eq!(
+            Bisect::candidates(&entries, &lock),
+            vec!["modrinth:indium", "modrinth:lithium"]
+        );
+    }
+
+    #[test]
+    fn state_narrows_down_to_the_culprit() {
+        let suspects: Vec<String> = (0..5).map(|i| format!("modrinth:mod{}", i)).collect();
+        let culprit = "modrinth:mod3";
+        let mut state = BisectState::new(suspects);
+        assert_eq!(state.steps_left(), 3);
+
+        let mut found = None;
+        for _ in 0..3 {
+            // The game works exactly when the culprit is disabled
+            let works = state.testing.iter().any(|k| k == culprit);
+            if let Some(c) = state.mark(works) {
+                found = Some(c);
+                break;
+            }
+        }
+
+        assert_eq!(found.as_deref(), Some(culprit));
+    }
+
+    fn lock_entry(key: &str, id: &str, deps: &[&str]) -> (String, LockEntry) {
+        (
+            key.to_string(),
+            LockEntry {
+                id: id.to_string(),
+                version: "1.0.0".into(),
+                version_id: None,
+                minecraft_versions: vec!["1.21.7".into()],
+                url: "https://example.invalid/mod.jar".into(),
+                hash: String::new(),
+                size: None,
+                dependencies: deps.iter().map(|d| d.to_string()).collect(),
+            },
+        )
+    }
+
+    fn mod_entry(slug: &str, disabled: bool) -> ModEntry {
+        ModEntry {
+            slug: slug.to_string(),
+            id: None,
+            version: VersionSpec::Exact("1.0.0".into()),
+            provider: Provider::Modrinth,
+            options: ModOptions {
+                disabled,
+                ..Default::default()
+            },
+        }
+    }
+}
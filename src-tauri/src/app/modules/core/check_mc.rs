@@ -0,0 +1,151 @@
+use futures::stream::{FuturesUnordered, StreamExt};
+use serde::Serialize;
+
+use crate::app::{
+    helpers::as_str,
+    modules::{
+        core::ops::manager::ModManager,
+        lock::{models::LockFile, services::LockService},
+        manifest::models::{Manifest, ModEntry, ModLoader},
+        minecraft::MinecraftService,
+        repositories::models::VersionResult,
+    },
+    McpmError,
+};
+
+/// Whether an entry has a version for the target Minecraft version and loader
+#[derive(Debug, Clone, Serialize)]
+pub struct McCompatibility {
+    pub key: String,
+    /// Locked version for the current Minecraft version
+    pub current: Option<String>,
+    /// Version the entry would resolve to for the target
+    pub compatible: Option<String>,
+    /// Newest version for the target on the allowed channel, whatever the version spec
+    pub latest: Option<String>,
+    /// Why there is no compatible version
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckMcReport {
+    pub minecraft_version: String,
+    pub modloader: ModLoader,
+    pub mods: Vec<McCompatibility>,
+}
+
+impl CheckMcReport {
+    /// Keys without a compatible version
+    pub fn incompatible(&self) -> Vec<String> {
+        self.mods
+            .iter()
+            .filter(|m| m.compatible.is_none())
+            .map(|m| m.key.clone())
+            .collect()
+    }
+}
+
+pub struct CheckMc;
+
+impl CheckMc {
+    /// Check every manifest entry against `minecraft_version` and `loader`
+    /// (the manifest's loader if `None`) without changing anything.
+    /// Snapshots are only accepted with `snapshot`.
+    pub async fn run(
+        minecraft_version: &str,
+        loader: Option<ModLoader>,
+        snapshot: bool,
+    ) -> Result<CheckMcReport, McpmError> {
+        MinecraftService::validate(minecraft_version, snapshot).await?;
+        let manager = ModManager::load().await?;
+        let target = Self::target(&manager.manifest, minecraft_version, loader);
+        let (report, _) = Self::check(&manager, &target).await?;
+        Ok(report)
+    }
+
+    /// The manifest as it would be for another Minecraft version and loader
+    pub(crate) fn target(
+        manifest: &Manifest,
+        minecraft_version: &str,
+        loader: Option<ModLoader>,
+    ) -> Manifest {
+        let mut target = manifest.clone();
+        target.minecraft_version = minecraft_version.to_string();
+        target.modloader = loader.unwrap_or(manifest.modloader);
+        target
+    }
+
+    /// Resolve every entry of `target`, the report together with the lock it would give
+    pub(crate) async fn check(
+        manager: &ModManager,
+        target: &Manifest,
+    ) -> Result<(CheckMcReport, LockService), McpmError> {
+        let mut entries = target.mods_as_entries();
+        entries.sort_by_key(|e| e.to_key());
+
+        // Fetch concurrently, resolve in order
+        let mut fetches = entries
+            .iter()
+            .enumerate()
+            .map(|(i, m)| async move {
+                let versions = manager
+                    .repo_service
+                    .get_versions(
+                        &manager.lock_service.project_ref(m),
+                        std::slice::from_ref(&target.minecraft_version),
+                        &[as_str(&target.modloader)],
+                    )
+                    .await;
+                (i, versions)
+            })
+            .collect::<FuturesUnordered<_>>();
+        let mut available: Vec<Vec<VersionResult>> = vec![vec![]; entries.len()];
+        while let Some((i, versions)) = fetches.next().await {
+            available[i] = versions?;
+        }
+
+        let mut resolved = LockService {
+            lock: LockFile::default(),
+        };
+        let mut mods = vec![];
+        for (entry, versions) in entries.iter().zip(&available) {
+            mods.push(Self::check_mod(manager, target, &mut resolved, entry, versions).await?);
+        }
+
+        let report = CheckMcReport {
+            minecraft_version: target.minecraft_version.clone(),
+            modloader: target.modloader,
+            mods,
+        };
+        Ok((report, resolved))
+    }
+
+    async fn check_mod(
+        manager: &ModManager,
+        target: &Manifest,
+        resolved: &mut LockService,
+        entry: &ModEntry,
+        versions: &[VersionResult],
+    ) -> Result<McCompatibility, McpmError> {
+        let channel = target.channel_for(entry);
+        let detail = match resolved
+            .update_entry(entry, target, &manager.repo_service, Some(versions), true)
+            .await
+        {
+            Ok(()) => None,
+            Err(McpmError::NoCompatibleVersion { detail, .. }) => Some(detail),
+            Err(e) => return Err(e),
+        };
+
+        Ok(McCompatibility {
+            key: entry.to_key(),
+            current: manager.lock_service.get_version(entry),
+            compatible: resolved.get_version(entry),
+            latest: versions
+                .iter()
+                .find(|v| v.version_type <= channel)
+                .map(|v| v.version.clone()),
+            detail,
+        })
+    }
+}
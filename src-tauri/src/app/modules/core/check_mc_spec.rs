@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use async_trait::async_trait;
+
+    use crate::app::{
+        modules::{
+            core::{check_mc::CheckMc, ops::manager::ModManager},
+            lock::{models::LockFile, services::LockService},
+            manifest::{
+                models::{Manifest, ModLoader, ModSpec, VersionSpec},
+                ManifestService,
+            },
+            repositories::{
+                models::{ModResult, VersionResult},
+                IRepository, RepositoryService,
+            },
+        },
+        McpmError,
+    };
+
+    /// Versions by project id and Minecraft version
+    struct FakeRepository;
+
+    #[async_trait]
+    impl IRepository for FakeRepository {
+        async fn search(&self, _: &str, _: usize) -> Result<Vec<ModResult>, McpmError> {
+            Ok(vec![])
+        }
+
+        async fn find(&self, _: &str) -> Result<Option<ModResult>, McpmError> {
+            Ok(None)
+        }
+
+        async fn get_versions(
+            &self,
+            project_id: &str,
+            game_versions: &[String],
+            loaders: &[String],
+        ) -> Result<Vec<VersionResult>, McpmError> {
+            let versions: &[&str] = match (project_id, game_versions[0].as_str()) {
+                _ if loaders != ["fabric"] => &[],
+                ("AANobbMI", "1.21.8") => &["0.7.0", "0.6.13"],
+                ("AANobbMI", _) => &["0.6.12"],
+                ("lithium", "1.21.8") => &[],
+                ("lithium", _) => &["0.14.0"],
+                _ => &[],
+            };
+            Ok(versions
+                .iter()
+                .map(|v| VersionResult {
+                    mod_id: project_id.to_string(),
+                    version: v.to_string(),
+                    minecraft_versions: game_versions.to_vec(),
+                    ..Default::default()
+                })
+                .collect())
+        }
+    }
+
+    fn manager() -> ModManager {
+        let mods = HashMap::from([
+            (
+                "modrinth:sodium".to_string(),
+                ModSpec {
+                    version: VersionSpec::Range("^0.6".into()),
+                    id: Some("AANobbMI".into()),
+                    options: Default::default(),
+                },
+            ),
+            (
+                "modrinth:lithium".to_string(),
+                ModSpec {
+                    version: VersionSpec::Range("^0.14".into()),
+                    id: None,
+                    options: Default::default(),
+                },
+            ),
+        ]);
+        ModManager {
+            manifest_service: ManifestService::at("mcpm.json".into()),
+            manifest: Manifest {
+                mods,
+                ..Default::default()
+            },
+            lock_service: LockService {
+                lock: LockFile::default(),
+            },
+            repo_service: RepositoryService::new()
+                .with_provider("modrinth", Box::new(FakeRepository)),
+        }
+    }
+
+    #[tokio::test]
+    async fn reports_which_mods_support_the_target() {
+        let manager = manager();
+        let target = CheckMc::target(&manager.manifest, "1.21.8", None);
+        let (report, resolved) = CheckMc::check(&manager, &target).await.unwrap();
+
+        assert_eq!(report.incompatible(), vec!["modrinth:lithium"]);
+        let sodium = &report.mods[1];
+        assert_eq!(sodium.key, "modrinth:sodium");
+        assert_eq!(sodium.compatible.as_deref(), Some("0.6.13"));
+        assert_eq!(sodium.latest.as_deref(), Some("0.7.0"));
+        assert!(report.mods[0].detail.is_some());
+        assert_eq!(resolved.lock.mods["modrinth:sodium"].version, "0.6.13");
+
+        let target = CheckMc::target(&manager.manifest, "1.21.7", None);
+        let (report, resolved) = CheckMc::check(&manager, &target).await.unwrap();
+        assert!(report.incompatible().is_empty());
+        assert_eq!(resolved.lock.mods.len(), 2);
+
+        let target = CheckMc::target(&manager.manifest, "1.21.7", Some(ModLoader::Quilt));
+        let (report, _) = CheckMc::check(&manager, &target).await.unwrap();
+        assert_eq!(report.incompatible().len(), 2);
+    }
+}
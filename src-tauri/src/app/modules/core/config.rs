@@ -0,0 +1,132 @@
+use serde::Serialize;
+use std::{fs, io, path::PathBuf};
+use toml_edit::DocumentMut;
+
+use crate::app::{
+    config::{is_key, set_key, ConfigFile, ConfigSource},
+    Config, McpmError,
+};
+
+/// An effective config value and the layer it comes from
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub value: String,
+    pub source: ConfigSource,
+}
+
+/// A value written to a config file by `mcpm config set`
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigChange {
+    pub key: String,
+    pub value: String,
+    pub path: PathBuf,
+}
+
+pub struct Configure;
+
+impl Configure {
+    /// Every effective value. API keys are masked.
+    pub fn list() -> Vec<ConfigEntry> {
+        Config::get()
+            .layers
+            .entries()
+            .into_iter()
+            .map(|(key, value, source)| ConfigEntry {
+                value: if key.starts_with("api_keys.") {
+                    mask(&value)
+                } else {
+                    value
+                },
+                key,
+                source,
+            })
+            .collect()
+    }
+
+    pub fn get(key: &str) -> Result<ConfigEntry, McpmError> {
+        if !is_key(key) {
+            return Err(McpmError::InvalidInput(format!(
+                "Unknown config key '{}'",
+                key
+            )));
+        }
+
+        Config::get()
+            .layers
+            .entries()
+            .into_iter()
+            .find(|(k, _, _)| k == key)
+            .map(|(key, value, source)| ConfigEntry { key, value, source })
+            .ok_or_else(|| McpmError::NotFound(format!("Config value '{}'", key)))
+    }
+
+    /// Write `key = value` to the project config file, or the user's one with `global`.
+    /// Comments and formatting of the file are kept.
+    pub fn set(key: &str, value: &str, global: bool) -> Result<ConfigChange, McpmError> {
+        let (path, table) = if global {
+            let path = Config::global_path().ok_or_else(|| {
+                McpmError::NotFound("Home directory for the global config".into())
+            })?;
+            (path, None)
+        } else {
+            Config::project_path(&Config::get().project_dir)
+        };
+
+        let content = match fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => {
+                return Err(McpmError::io(
+                    format!("Failed to read {}", path.display()),
+                    e,
+                ))
+            }
+        };
+        let parse_error = |source| McpmError::ConfigParse {
+            path: path.clone(),
+            source,
+        };
+
+        // Parsing with `toml` first gives the same errors as loading the file
+        ConfigFile::parse(&content, table).map_err(parse_error)?;
+        let mut doc: DocumentMut = content.parse().expect("valid TOML parses as a document");
+
+        let target = match table {
+            None => doc.as_table_mut(),
+            Some(t) => doc
+                .entry(t)
+                .or_insert_with(toml_edit::table)
+                .as_table_mut()
+                .ok_or_else(|| McpmError::InvalidInput(format!("'{}' is not a table", t)))?,
+        };
+        set_key(target, key, value).map_err(McpmError::InvalidInput)?;
+
+        let content = doc.to_string();
+        ConfigFile::parse(&content, table)
+            .map_err(|e| McpmError::InvalidInput(format!("{}: {}", key, e.message())))?;
+
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .map_err(|e| McpmError::io(format!("Failed to create {}", parent.display()), e))?;
+        }
+        fs::write(&path, content)
+            .map_err(|e| McpmError::io(format!("Failed to write {}", path.display()), e))?;
+
+        Ok(ConfigChange {
+            key: key.to_string(),
+            value: value.to_string(),
+            path,
+        })
+    }
+}
+
+/// Hide all but the last four characters of a secret
+fn mask(secret: &str) -> String {
+    let chars: Vec<char> = secret.chars().collect();
+    if chars.len() <= 8 {
+        return "****".into();
+    }
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("****{}", tail)
+}
@@ -0,0 +1,102 @@
+use serde::Serialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+use toml_edit::DocumentMut;
+
+use crate::app::{
+    modules::{
+        io::use_io,
+        manifest::{ManifestFormat, ManifestService},
+    },
+    McpmError,
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConvertResult {
+    pub from: PathBuf,
+    pub to: PathBuf,
+    /// False when the manifest already was in the requested format
+    pub converted: bool,
+    /// Where the `[config]` table of mcpm.toml was moved, when it had one
+    pub config: Option<PathBuf>,
+}
+
+pub struct Convert;
+
+impl Convert {
+    /// Rewrite the manifest in `format` next to the current one, then remove the old file.
+    /// The `[config]` table of mcpm.toml moves to `.mcpmrc`, which JSON manifests leave to it.
+    pub fn run(format: ManifestFormat) -> Result<ConvertResult, McpmError> {
+        Self::run_at(ManifestService::new(), format)
+    }
+
+    /// [`Self::run`] for the manifest `service` points at
+    pub fn run_at(
+        service: ManifestService,
+        format: ManifestFormat,
+    ) -> Result<ConvertResult, McpmError> {
+        let from = service.path().to_path_buf();
+        if service.format() == format {
+            return Ok(ConvertResult {
+                to: from.clone(),
+                from,
+                converted: false,
+                config: None,
+            });
+        }
+
+        let manifest = service.load()?;
+        let to = from.with_file_name(format.file_name());
+        if to.exists() {
+            return Err(McpmError::Conflict(format!(
+                "{} already exists",
+                to.display()
+            )));
+        }
+
+        let mut config = None;
+        if service.format() == ManifestFormat::Toml {
+            if let Some(table) = Self::config_table(&from)? {
+                let rc = from.with_file_name(".mcpmrc");
+                if rc.exists() {
+                    return Err(McpmError::Conflict(format!(
+                        "{} has a [config] table and {} already exists, merge them first",
+                        from.display(),
+                        rc.display()
+                    )));
+                }
+                fs::write(&rc, table)
+                    .map_err(|e| McpmError::io(format!("Failed to write {}", rc.display()), e))?;
+                config = Some(rc);
+            }
+            use_io().warn("Comments of mcpm.toml are not kept in JSON");
+        }
+
+        ManifestService::at(to.clone())
+            .save(&manifest)
+            .map_err(|e| McpmError::io(format!("Failed to write {}", to.display()), e))?;
+        fs::remove_file(&from)
+            .map_err(|e| McpmError::io(format!("Failed to remove {}", from.display()), e))?;
+
+        Ok(ConvertResult {
+            from,
+            to,
+            converted: true,
+            config,
+        })
+    }
+
+    /// The `[config]` table of a TOML manifest as a config file of its own, `None` when empty
+    fn config_table(path: &Path) -> Result<Option<String>, McpmError> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| McpmError::io(format!("Failed to read {}", path.display()), e))?;
+        let mut doc: DocumentMut = content.parse().expect("valid TOML parses as a document");
+        let table = doc
+            .remove("config")
+            .and_then(|item| item.into_table().ok())
+            .filter(|table| !table.is_empty());
+        Ok(table.map(|t| DocumentMut::from(t).to_string()))
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use crate::app::{
+        config::ConfigFile,
+        modules::{
+            core::{convert::Convert, test_workspace::workspace},
+            manifest::{ManifestFormat, ManifestService},
+        },
+        McpmError,
+    };
+
+    const TOML_MANIFEST: &str = r#"name = "Pack"
+version = "1.0.0"
+minecraft_version = "1.21.7"
+
+[config]
+# slow link
+concurrency = 2
+
+[mods]
+"modrinth:sodium" = "^0.6"
+"#;
+
+    #[tokio::test]
+    async fn manifests_convert_both_ways() {
+        let (_workspace, root) = workspace().await;
+        let json = root.join("mcpm.json");
+        let toml = root.join("mcpm.toml");
+        fs::write(
+            &json,
+            r#"{ "name": "Pack", "minecraft_version": "1.21.7", "mods": { "modrinth:sodium": "^0.6" } }"#,
+        )
+        .unwrap();
+
+        let result = Convert::run(ManifestFormat::Toml).unwrap();
+        assert!(result.converted);
+        assert!(result.config.is_none());
+        assert!(!json.exists());
+        let manifest = ManifestService::at(toml.clone()).load().unwrap();
+        assert!(manifest.mods.contains_key("modrinth:sodium"));
+
+        let result =
+            Convert::run_at(ManifestService::at(toml.clone()), ManifestFormat::Json).unwrap();
+        assert!(result.converted);
+        assert!(!toml.exists());
+        let manifest = ManifestService::at(json.clone()).load().unwrap();
+        assert!(manifest.mods.contains_key("modrinth:sodium"));
+
+        // Already JSON
+        assert!(!Convert::run(ManifestFormat::Json).unwrap().converted);
+    }
+
+    #[tokio::test]
+    async fn the_config_table_moves_to_mcpmrc() {
+        let (_workspace, root) = workspace().await;
+        let toml = root.join("mcpm.toml");
+        let rc = root.join(".mcpmrc");
+        fs::write(&toml, TOML_MANIFEST).unwrap();
+
+        let result =
+            Convert::run_at(ManifestService::at(toml.clone()), ManifestFormat::Json).unwrap();
+        assert_eq!(result.config.as_deref(), Some(rc.as_path()));
+        let config = ConfigFile::load(&rc, None).unwrap().unwrap();
+        assert_eq!(config.concurrency, Some(2));
+        assert!(fs::read_to_string(&rc).unwrap().contains("# slow link"));
+        assert!(!toml.exists());
+    }
+
+    #[tokio::test]
+    async fn the_config_table_is_not_dropped_over_an_existing_mcpmrc() {
+        let (_workspace, root) = workspace().await;
+        let toml = root.join("mcpm.toml");
+        let rc = root.join(".mcpmrc");
+        fs::write(&toml, TOML_MANIFEST).unwrap();
+        fs::write(&rc, "concurrency = 8\n").unwrap();
+
+        let err =
+            Convert::run_at(ManifestService::at(toml.clone()), ManifestFormat::Json).unwrap_err();
+        assert!(matches!(err, McpmError::Conflict(_)));
+        assert_eq!(fs::read_to_string(&toml).unwrap(), TOML_MANIFEST);
+        assert_eq!(fs::read_to_string(&rc).unwrap(), "concurrency = 8\n");
+        assert!(!root.join("mcpm.json").exists());
+    }
+}
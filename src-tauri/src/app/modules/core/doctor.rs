@@ -0,0 +1,105 @@
+use serde::Serialize;
+
+use crate::app::{
+    modules::{
+        core::ops::manager::ModManager, manifest::models::ModEntry, repositories::models::ModResult,
+    },
+    McpmError,
+};
+
+/// A manifest key whose slug was renamed at the provider
+#[derive(Debug, Clone, Serialize)]
+pub struct SlugRename {
+    pub from: String,
+    pub to: String,
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DoctorReport {
+    pub renamed: Vec<SlugRename>,
+    /// Keys the provider knows neither by id nor by slug
+    pub missing: Vec<String>,
+    /// Keys without a recorded project id
+    pub missing_ids: Vec<String>,
+    /// Whether the manifest and lock were updated
+    pub fixed: bool,
+}
+
+pub struct Doctor;
+
+impl Doctor {
+    /// Look every entry up by its id (or slug, while none is recorded) to find renamed slugs.
+    /// With `fix_slugs`, renamed keys are updated in the manifest and lock, and missing ids recorded.
+    pub async fn run(fix_slugs: bool) -> Result<DoctorReport, McpmError> {
+        Self::run_with(ModManager::load().await?, fix_slugs).await
+    }
+
+    /// [`Self::run`] on an already loaded manager
+    pub async fn run_with(
+        mut manager: ModManager,
+        fix_slugs: bool,
+    ) -> Result<DoctorReport, McpmError> {
+        let mut report = DoctorReport::default();
+
+        let mut entries = manager.manifest.mods_as_entries();
+        entries.sort_by_key(|e| e.to_key());
+
+        let mut found: Vec<(ModEntry, ModResult)> = vec![];
+        for entry in entries {
+            let key = entry.to_key();
+            let Some(project) = manager
+                .repo_service
+                .find(&manager.lock_service.project_ref(&entry))
+                .await?
+            else {
+                report.missing.push(key);
+                continue;
+            };
+
+            if entry.id.is_none() {
+                report.missing_ids.push(key.clone());
+            }
+            if project.slug != entry.slug {
+                report.renamed.push(SlugRename {
+                    from: key,
+                    to: format!("{}:{}", entry.provider, project.slug),
+                    id: project.id.clone(),
+                });
+            }
+            found.push((entry, project));
+        }
+
+        if !fix_slugs || (report.renamed.is_empty() && report.missing_ids.is_empty()) {
+            return Ok(report);
+        }
+
+        if let Some(clash) = report
+            .renamed
+            .iter()
+            .find(|r| manager.manifest.mods.contains_key(&r.to))
+        {
+            return Err(McpmError::Conflict(format!(
+                "{} was renamed to {}, which is already in the manifest",
+                clash.from, clash.to
+            )));
+        }
+
+        for (mut entry, project) in found {
+            let old_key = entry.to_key();
+            manager.manifest.mods.remove(&old_key);
+            entry.slug = project.slug;
+            entry.id = Some(project.id);
+            manager.manifest.insert_mod_entry(&entry);
+
+            let lock = &mut manager.lock_service.lock.mods;
+            if let Some(locked) = lock.remove(&old_key) {
+                lock.insert(entry.to_key(), locked);
+            }
+        }
+        manager.save_all()?;
+        report.fixed = true;
+
+        Ok(report)
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use serde_json::json;
+
+    use crate::app::{
+        modules::{
+            core::{
+                doctor::Doctor,
+                test_workspace::{manager, workspace, FakeRepository},
+            },
+            manifest::models::Side,
+            repositories::models::ModResult,
+        },
+        McpmError,
+    };
+
+    /// `old-sodium` was renamed to `sodium`, lithium is unchanged and `gone` was deleted
+    fn make_pack(root: &Path, extra: Option<&str>) {
+        let mut manifest = json!({
+            "name": "Pack",
+            "minecraft_version": "1.21.7",
+            "mods": {
+                "modrinth:old-sodium": "^0.6",
+                "modrinth:lithium": { "version": "^0.14", "id": "gvQqBUqZ" },
+                "modrinth:gone": "^1.0"
+            }
+        });
+        if let Some(key) = extra {
+            manifest["mods"][key] = json!("^1.0");
+        }
+        let lock = json!({
+            "mods": {
+                "modrinth:old-sodium": {
+                    "id": "AANobbMI",
+                    "version": "0.6.13",
+                    "minecraft_versions": ["1.21.7"],
+                    "url": "https://example.invalid/sodium.jar",
+                    "hash": "00"
+                }
+            }
+        });
+        fs::write(root.join("mcpm.json"), manifest.to_string()).unwrap();
+        fs::write(root.join("mcpm.lock"), lock.to_string()).unwrap();
+    }
+
+    fn repository() -> FakeRepository {
+        let project = |id: &str, slug: &str| ModResult {
+            id: id.into(),
+            slug: slug.into(),
+            name: slug.into(),
+            description: String::new(),
+            source: "modrinth".into(),
+            side: Side::Both,
+            url: String::new(),
+        };
+        FakeRepository {
+            projects: vec![
+                project("AANobbMI", "sodium"),
+                project("gvQqBUqZ", "lithium"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn written(root: &Path, file: &str) -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(root.join(file)).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn fix_slugs_renames_keys_and_records_ids() {
+        let (_workspace, root) = workspace().await;
+        make_pack(&root, None);
+
+        // Only a report without the fix
+        let report = Doctor::run_with(manager(repository()), false)
+            .await
+            .unwrap();
+        assert!(!report.fixed);
+        assert_eq!(report.renamed.len(), 1);
+        assert_eq!(report.renamed[0].from, "modrinth:old-sodium");
+        assert_eq!(report.renamed[0].to, "modrinth:sodium");
+        assert_eq!(report.missing, vec!["modrinth:gone"]);
+        assert_eq!(report.missing_ids, vec!["modrinth:old-sodium"]);
+        assert!(written(&root, "mcpm.json")["mods"]
+            .get("modrinth:old-sodium")
+            .is_some());
+
+        let report = Doctor::run_with(manager(repository()), true).await.unwrap();
+        assert!(report.fixed);
+
+        let mods = &written(&root, "mcpm.json")["mods"];
+        assert!(mods.get("modrinth:old-sodium").is_none());
+        assert_eq!(mods["modrinth:sodium"]["id"], "AANobbMI");
+        assert_eq!(mods["modrinth:lithium"]["id"], "gvQqBUqZ");
+        let locked = &written(&root, "mcpm.lock")["mods"];
+        assert!(locked.get("modrinth:old-sodium").is_none());
+        assert_eq!(locked["modrinth:sodium"]["version"], "0.6.13");
+    }
+
+    #[tokio::test]
+    async fn fix_slugs_refuses_renames_onto_existing_keys() {
+        let (_workspace, root) = workspace().await;
+        make_pack(&root, Some("modrinth:sodium"));
+        let before = fs::read_to_string(root.join("mcpm.json")).unwrap();
+
+        let err = Doctor::run_with(manager(repository()), true)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, McpmError::Conflict(_)));
+        assert_eq!(fs::read_to_string(root.join("mcpm.json")).unwrap(), before);
+    }
+}
@@ -0,0 +1,9 @@
+use crate::app::modules::snapshot::{models::Snapshot, SnapshotService};
+
+pub struct History;
+
+impl History {
+    pub fn run() -> Vec<Snapshot> {
+        SnapshotService::new().list()
+    }
+}
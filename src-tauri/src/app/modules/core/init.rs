@@ -1,16 +1,42 @@
-use crate::app::modules::{io::use_io, manifest::ManifestService};
+use crate::app::{
+    modules::{
+        io::use_io,
+        manifest::{models::Manifest, ManifestService},
+        minecraft::MinecraftService,
+    },
+    McpmError,
+};
 pub struct Init;
 
 impl Init {
-    pub async fn run() {
+    /// Create the manifest for `minecraft_version`, by default the latest release
+    /// (or snapshot with `snapshot`), or normalize an existing one.
+    pub async fn run(minecraft_version: Option<String>, snapshot: bool) -> Result<(), McpmError> {
         let io = use_io();
         let service = ManifestService::new();
 
-        if let Err(e) = service.init() {
-            io.error("Initialization failed", Some(&e));
-            return;
-        }
+        let minecraft_version = if service.path().exists() {
+            if minecraft_version.is_some() {
+                io.info("Keeping the Minecraft version of the existing manifest, change it with `mcpm migrate --mc`");
+            }
+            None
+        } else if let Some(v) = minecraft_version {
+            MinecraftService::validate(&v, snapshot).await?;
+            Some(v)
+        } else {
+            let latest = MinecraftService::latest(snapshot).await;
+            if latest.is_none() {
+                io.warn(&format!(
+                    "Couldn't read Mojang's version list, using Minecraft {}",
+                    Manifest::default().minecraft_version
+                ));
+            }
+            latest
+        };
+
+        service.init(minecraft_version)?;
 
         io.success("Initialization complete.");
+        Ok(())
     }
 }
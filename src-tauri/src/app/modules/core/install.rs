@@ -1,104 +1,531 @@
+use futures::stream::{self, StreamExt, TryStreamExt};
 use reqwest::Client;
+use serde::Serialize;
 use sha2::{Digest, Sha512};
 use std::{
-    env, fs,
+    collections::HashMap,
+    fs,
     io::Write,
     path::{Path, PathBuf},
 };
 
 // modules/core/install.rs
 use crate::app::{
-    modules::{core::ops::manager::ModManager, io::use_io},
-    Config,
+    helpers::http,
+    modules::{
+        core::ops::manager::ModManager,
+        io::{
+            traits::{ProgressId, ProgressUnit},
+            use_io,
+        },
+        lock::{
+            models::{LockEntry, LockFile},
+            services::LockService,
+        },
+        manifest::{
+            models::{Manifest, Side},
+            ManifestService,
+        },
+        snapshot::SnapshotService,
+    },
+    Config, McpmError,
 };
 
-pub struct Install;
+/// A lock entry that will be added, changed or dropped by an install.
+#[derive(Debug, Clone, Serialize)]
+pub struct LockChange {
+    pub key: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
 
-impl Install {
-    pub async fn run(no_cache: bool, force_rehash: bool) -> Result<(), String> {
-        let io = use_io();
+/// How the manifest wants a locked mod placed in the mods directory
+#[derive(Debug, Clone, Default)]
+pub struct Placement {
+    /// Installed as `.jar.disabled`
+    pub disabled: bool,
+    /// File name instead of `<key>-<version>.jar`
+    pub filename: Option<String>,
+    /// Left out, the mod is for the other side
+    pub skipped: bool,
+    /// Left out, the mod is optional and was not selected
+    pub left_out: bool,
+}
 
-        let mut manager = ModManager::load()
-            .await
-            .map_err(|e| format!("Failed to initialize ModManager: {}", e))?;
+/// Placements by lock key. Keys without one are installed under their default name.
+pub type Placements = HashMap<String, Placement>;
 
-        let mods = manager.manifest.mods_as_entries();
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedDownload {
+    pub key: String,
+    pub version: String,
+    pub url: String,
+    pub hash: String,
+    pub size: Option<u64>,
+    pub path: PathBuf,
+}
 
-        for entry in &mods {
-            manager
-                .refresh_mod(entry, None, false)
-                .await
-                .map_err(|e| format!("Failed to resolve {}: {}", entry.slug, e))?;
-        }
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedCopy {
+    pub key: String,
+    pub from: PathBuf,
+    pub to: PathBuf,
+    pub size: Option<u64>,
+}
+
+/// Everything an install would do, computed without touching disk.
+#[derive(Debug, Clone, Serialize)]
+pub struct InstallPlan {
+    pub lock_changes: Vec<LockChange>,
+    pub downloads: Vec<PlannedDownload>,
+    pub copies: Vec<PlannedCopy>,
+    pub deletions: Vec<PathBuf>,
+    pub cache_dir: PathBuf,
+    pub mods_dir: PathBuf,
+    /// Optional mods that are not installed, selected with `install --with`
+    pub left_out: Vec<String>,
+    /// Optional mods picked with `--with`, now or by an earlier install
+    pub selected: Vec<String>,
 
-        manager
-            .save_all()
-            .map_err(|e| format!("Failed to save state: {}", e))?;
+    /// The resolved lock that gets written when the plan is executed
+    #[serde(skip)]
+    pub lock: LockFile,
 
-        let cache_dir = &Config::get().cache_dir;
-        let mods_dir = &Config::get().mods_dir;
-        fs::create_dir_all(&cache_dir).ok();
-        fs::create_dir_all(&mods_dir).ok();
+    /// The manifest written with the lock, on the current schema and with the
+    /// project ids from the lock. `None` leaves the file as it is.
+    #[serde(skip)]
+    pub manifest: Option<Manifest>,
+}
+
+/// Optional mods picked with `--with`, kept for later installs, rollbacks and bisects
+const SELECTION_FILE: &str = ".mcpm/optional.json";
+
+impl InstallPlan {
+    pub fn is_empty(&self) -> bool {
+        self.lock_changes.is_empty()
+            && self.downloads.is_empty()
+            && self.copies.is_empty()
+            && self.deletions.is_empty()
+    }
+
+    /// Short description of the lock changes, e.g. "sodium 0.5.8 → 0.5.11, +lithium"
+    pub fn summary(&self) -> String {
+        if self.lock_changes.is_empty() {
+            return "no lock changes".into();
+        }
+        self.lock_changes
+            .iter()
+            .map(|c| match (&c.before, &c.after) {
+                (Some(b), Some(a)) => format!("{} {} → {}", c.key, b, a),
+                (None, _) => format!("+{}", c.key),
+                (_, None) => format!("-{}", c.key),
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Total number of bytes to download, counting only downloads with a known size
+    pub fn download_size(&self) -> u64 {
+        self.downloads.iter().filter_map(|d| d.size).sum()
+    }
 
+    /// Build a plan from the lock before and after resolution.
+    /// Mods in `disabled` are installed as `.jar.disabled`.
+    /// Only reads from `mods_dir` and `cache_dir`.
+    pub fn build(
+        before: &LockFile,
+        lock: LockFile,
+        placements: &Placements,
+        mods_dir: &Path,
+        cache_dir: &Path,
+        no_cache: bool,
+        force_rehash: bool,
+    ) -> Result<Self, McpmError> {
         // Fail if one of the existing mods have an invalid hash.
         if !force_rehash {
-            for (key, entry) in &manager.lock_service.lock.mods {
-                let mod_path = mods_dir.join(format!("{}-{}.jar", key, entry.version));
-                let cache_path = cache_dir.join(format!("{}-{}.jar", key, entry.version));
-
-                for p in [&mod_path, &cache_path] {
-                    if p.exists() && !Self::verify_file_hash(p, &entry.hash)? {
-                        return Err(format!(
-                            "Hash mismatch for {}. Re-run with --force-rehash to continue.",
-                            key
-                        ));
+            for (key, entry) in &lock.mods {
+                let file_name = entry.file_name(key);
+                let target_name = Install::target_name(key, entry, placements.get(key));
+                for p in [mods_dir.join(&target_name), cache_dir.join(&file_name)] {
+                    if p.exists() && !Install::verify_file_hash(&p, &entry.hash)? {
+                        return Err(McpmError::HashMismatch { path: p });
                     }
                 }
             }
         }
 
+        let mut lock_changes: Vec<LockChange> = lock
+            .mods
+            .iter()
+            .filter_map(|(key, entry)| {
+                let prev = before.mods.get(key).map(|e| e.version.clone());
+                (prev.as_ref() != Some(&entry.version)).then(|| LockChange {
+                    key: key.clone(),
+                    before: prev,
+                    after: Some(entry.version.clone()),
+                })
+            })
+            .chain(
+                before
+                    .mods
+                    .iter()
+                    .filter(|(key, _)| !lock.mods.contains_key(*key))
+                    .map(|(key, entry)| LockChange {
+                        key: key.clone(),
+                        before: Some(entry.version.clone()),
+                        after: None,
+                    }),
+            )
+            .collect();
+        lock_changes.sort_by(|a, b| a.key.cmp(&b.key));
+
+        let mut downloads = Vec::new();
+        let mut copies = Vec::new();
         let mut expected_mod_files = Vec::<PathBuf>::new();
 
-        // 2. Download mods
-        for (key, entry) in &manager.lock_service.lock.mods {
-            let file_name = format!("{}-{}.jar", key, entry.version);
-            let target_path = mods_dir.join(&file_name);
-            let cache_path = cache_dir.join(&file_name);
+        let mut keys: Vec<_> = lock.mods.keys().collect();
+        keys.sort();
+
+        for key in keys {
+            let entry = &lock.mods[key];
+            let placement = placements.get(key);
+            if placement.is_some_and(|p| p.skipped || p.left_out) {
+                continue;
+            }
+            let target_path = mods_dir.join(Install::target_name(key, entry, placement));
+            let cache_path = cache_dir.join(entry.file_name(key));
             expected_mod_files.push(target_path.clone());
 
             let dest = if no_cache { &target_path } else { &cache_path };
-            if !dest.exists() || force_rehash {
-                io.info(&format!("Downloading {} {}", key, entry.version));
-                Self::download_to(dest, &entry.url, &entry.hash).await?;
+            let download = !dest.exists() || force_rehash;
+            if download {
+                downloads.push(PlannedDownload {
+                    key: key.clone(),
+                    version: entry.version.clone(),
+                    url: entry.url.clone(),
+                    hash: entry.hash.clone(),
+                    size: entry.size,
+                    path: dest.clone(),
+                });
             }
 
-            if !no_cache {
-                fs::copy(&cache_path, &target_path)
-                    .map_err(|e| format!("Copy failed for {}: {}", key, e))?;
+            if !no_cache && (download || !target_path.exists()) {
+                let size = fs::metadata(&cache_path).ok().map(|m| m.len());
+                copies.push(PlannedCopy {
+                    key: key.clone(),
+                    from: cache_path,
+                    to: target_path,
+                    size: size.or(entry.size),
+                });
             }
         }
 
-        // 3. Remove outdated mod files
-        for entry in
-            fs::read_dir(&mods_dir).map_err(|e| format!("Failed to read mods directory: {}", e))?
-        {
-            let path = entry.map_err(|e| e.to_string())?.path();
-            if path.is_file() {
-                if !expected_mod_files.contains(&path) {
-                    fs::remove_file(&path)
-                        .map_err(|e| format!("Failed to remove outdated mod {:?}: {}", path, e))?;
+        // Outdated mod files
+        let mut deletions = Vec::new();
+        if mods_dir.exists() {
+            let read_error = |e| McpmError::io("Failed to read mods directory", e);
+            for entry in fs::read_dir(mods_dir).map_err(read_error)? {
+                let path = entry.map_err(read_error)?.path();
+                if path.is_file() && !expected_mod_files.contains(&path) {
+                    deletions.push(path);
                 }
             }
         }
+        deletions.sort();
+
+        let mut left_out: Vec<String> = placements
+            .iter()
+            .filter(|(key, p)| p.left_out && lock.mods.contains_key(*key))
+            .map(|(key, _)| key.clone())
+            .collect();
+        left_out.sort();
+
+        Ok(Self {
+            lock_changes,
+            downloads,
+            copies,
+            deletions,
+            cache_dir: cache_dir.to_path_buf(),
+            mods_dir: mods_dir.to_path_buf(),
+            left_out,
+            selected: vec![],
+            lock,
+            manifest: None,
+        })
+    }
+}
+
+pub struct Install;
+
+impl Install {
+    pub async fn run(
+        no_cache: bool,
+        force_rehash: bool,
+        with: &[String],
+    ) -> Result<InstallPlan, McpmError> {
+        let plan = Self::plan(no_cache, force_rehash, with).await?;
+        Self::execute(&plan).await?;
+        Ok(plan)
+    }
+
+    /// Resolve the lock against the manifest and compute the install plan.
+    /// Network is only used for mods whose lock entry no longer satisfies the manifest.
+    /// Optional mods are left out unless picked earlier or named in `with`, by key or slug.
+    pub async fn plan(
+        no_cache: bool,
+        force_rehash: bool,
+        with: &[String],
+    ) -> Result<InstallPlan, McpmError> {
+        let manager = ModManager::load().await?;
+        let before = manager.lock_service.lock.clone();
+
+        Self::plan_with(manager, &before, no_cache, force_rehash, with).await
+    }
+
+    pub(crate) async fn plan_with(
+        mut manager: ModManager,
+        before: &LockFile,
+        no_cache: bool,
+        force_rehash: bool,
+        with: &[String],
+    ) -> Result<InstallPlan, McpmError> {
+        let mods = manager.manifest.mods_as_entries();
+        let selected = Self::pick(&manager.manifest, &Self::load_selection()?, with)?;
+        let placements = Self::placements(&manager.manifest, &selected)?;
+
+        manager.validate_minecraft(&mods, false).await?;
+        for entry in &mods {
+            manager.refresh_mod(entry, None, false).await?;
+        }
+        manager.refresh_loader(false).await?;
+        manager.record_ids();
+
+        let config = Config::get();
+        let plan = InstallPlan::build(
+            before,
+            manager.lock_service.lock,
+            &placements,
+            &config.mods_dir,
+            &config.cache_dir,
+            no_cache,
+            force_rehash,
+        )?;
+        Ok(InstallPlan {
+            manifest: Some(manager.manifest),
+            selected,
+            ..plan
+        })
+    }
+
+    /// Plan an install of the lock on disk as-is, placed as `placements` say.
+    /// Nothing is resolved, so no network is needed for cached mods.
+    pub fn plan_locked(placements: &Placements) -> Result<InstallPlan, McpmError> {
+        let lock = LockService::load()?.lock;
+        let config = Config::get();
+        InstallPlan::build(
+            &lock,
+            lock.clone(),
+            placements,
+            &config.mods_dir,
+            &config.cache_dir,
+            false,
+            false,
+        )
+    }
+
+    /// Snapshot the current state, then apply the plan.
+    pub async fn execute(plan: &InstallPlan) -> Result<(), McpmError> {
+        if !plan.is_empty() {
+            let snapshot = SnapshotService::new()
+                .capture(
+                    ManifestService::new().path(),
+                    &LockService::path(),
+                    &plan.cache_dir,
+                    &plan.summary(),
+                )
+                .map_err(|e| McpmError::io("Failed to record snapshot", e))?;
+            use_io().debug(&format!("Recorded snapshot {}", snapshot.id));
+        }
+
+        Self::apply(plan).await
+    }
+
+    /// Apply a plan: write the manifest and lock, download, copy from cache and remove outdated files.
+    pub async fn apply(plan: &InstallPlan) -> Result<(), McpmError> {
+        let io = use_io();
+
+        if let Some(manifest) = &plan.manifest {
+            ManifestService::new()
+                .save(manifest)
+                .map_err(|e| McpmError::io("Failed to save manifest", e))?;
+            Self::save_selection(&plan.selected)?;
+        }
+        LockService {
+            lock: plan.lock.clone(),
+        }
+        .save()
+        .map_err(|e| McpmError::io("Failed to save lockfile", e))?;
+
+        fs::create_dir_all(&plan.cache_dir).ok();
+        fs::create_dir_all(&plan.mods_dir).ok();
+
+        if !plan.downloads.is_empty() {
+            let client = http::client();
+            let overall = io.progress_start(
+                "Downloading mods",
+                Some(plan.downloads.len() as u64),
+                ProgressUnit::Items,
+            );
+
+            // Owned items keep the stream's future `Send`, which borrowed items do not
+            let result = stream::iter(plan.downloads.iter().cloned())
+                .map(|d| {
+                    let client = &client;
+                    let io = &io;
+                    async move {
+                        io.debug(&format!("Downloading {} {}", d.key, d.version));
+                        let bar = io.progress_start(
+                            &format!("{} {}", d.key, d.version),
+                            d.size,
+                            ProgressUnit::Bytes,
+                        );
+                        let result = Self::download_with(client, &d, bar).await;
+                        io.progress_finish(bar);
+                        io.progress_advance(overall, 1);
+                        result
+                    }
+                })
+                .buffer_unordered(Config::get().concurrency)
+                .try_collect::<Vec<_>>()
+                .await;
+
+            io.progress_finish(overall);
+            result?;
+        }
+
+        for c in &plan.copies {
+            fs::copy(&c.from, &c.to)
+                .map_err(|e| McpmError::io(format!("Copy failed for {}", c.key), e))?;
+        }
+
+        for path in &plan.deletions {
+            fs::remove_file(path).map_err(|e| {
+                McpmError::io(format!("Failed to remove outdated mod {:?}", path), e)
+            })?;
+        }
 
         Ok(())
     }
 
-    fn verify_file_hash(path: &Path, expected: &str) -> Result<bool, String> {
-        use sha2::{Digest, Sha512};
-        use std::fs;
+    /// Keys of the optional mods to install: those `saved` by earlier installs that
+    /// are still in the manifest, plus those `with` names by key or slug
+    pub fn pick(
+        manifest: &Manifest,
+        saved: &[String],
+        with: &[String],
+    ) -> Result<Vec<String>, McpmError> {
+        let entries = manifest.mods_as_entries();
+        let mut picked = saved
+            .iter()
+            .filter(|k| manifest.mods.contains_key(*k))
+            .cloned()
+            .collect::<Vec<_>>();
+        for name in with {
+            let entry = entries
+                .iter()
+                .find(|m| m.to_key() == *name || m.slug == *name)
+                .ok_or_else(|| McpmError::NotFound(format!("Mod '{}'", name)))?;
+            picked.push(entry.to_key());
+        }
+        picked.sort();
+        picked.dedup();
+        Ok(picked)
+    }
+
+    /// Optional mods picked so far, see [`SELECTION_FILE`]
+    pub fn load_selection() -> Result<Vec<String>, McpmError> {
+        match fs::read_to_string(Self::selection_path()) {
+            Ok(content) => serde_json::from_str(&content).map_err(|e| {
+                McpmError::InvalidInput(format!("Corrupt optional mod selection: {}", e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+            Err(e) => Err(McpmError::io("Failed to read optional mod selection", e)),
+        }
+    }
 
-        let bytes = fs::read(path).map_err(|e| e.to_string())?;
+    fn save_selection(selected: &[String]) -> Result<(), McpmError> {
+        let path = Self::selection_path();
+        let save_error = |e| McpmError::io("Failed to save optional mod selection", e);
+        if selected.is_empty() {
+            return match fs::remove_file(&path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(save_error(e)),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(save_error)?;
+        }
+        let json = serde_json::to_string_pretty(selected).expect("selection serializes");
+        fs::write(path, json).map_err(save_error)
+    }
+
+    fn selection_path() -> PathBuf {
+        Config::get().project_dir.join(SELECTION_FILE)
+    }
+
+    /// Where the manifest's entries go in the mods directory.
+    /// Mods for the other side are skipped when the pack is client or server only,
+    /// optional mods unless their key is `selected`, see [`Self::pick`].
+    pub fn placements(manifest: &Manifest, selected: &[String]) -> Result<Placements, McpmError> {
+        let mut placements = Placements::new();
+        for m in manifest.mods_as_entries() {
+            let filename = m.options.filename.clone();
+            if let Some(name) = &filename {
+                let plain = Path::new(name)
+                    .file_name()
+                    .is_some_and(|n| n == name.as_str());
+                if !plain {
+                    return Err(McpmError::InvalidInput(format!(
+                        "filename '{}' of {} must be a plain file name",
+                        name,
+                        m.to_key()
+                    )));
+                }
+            }
+
+            let skipped = matches!(
+                (&manifest.side, &m.options.side),
+                (Side::Client, Some(Side::Server)) | (Side::Server, Some(Side::Client))
+            );
+            let left_out = m.options.optional && !selected.contains(&m.to_key());
+            placements.insert(
+                m.to_key(),
+                Placement {
+                    disabled: m.options.disabled,
+                    filename,
+                    skipped,
+                    left_out,
+                },
+            );
+        }
+        Ok(placements)
+    }
+
+    /// File name in the mods directory, `.jar.disabled` for disabled mods
+    fn target_name(key: &str, entry: &LockEntry, placement: Option<&Placement>) -> String {
+        let file_name = placement
+            .and_then(|p| p.filename.clone())
+            .unwrap_or_else(|| entry.file_name(key));
+        if placement.is_some_and(|p| p.disabled) {
+            format!("{}.disabled", file_name)
+        } else {
+            file_name
+        }
+    }
+
+    fn verify_file_hash(path: &Path, expected: &str) -> Result<bool, McpmError> {
+        let bytes = fs::read(path)
+            .map_err(|e| McpmError::io(format!("Failed to read {}", path.display()), e))?;
         let mut hasher = Sha512::new();
         hasher.update(&bytes);
         let actual = format!("{:x}", hasher.finalize());
@@ -106,23 +533,40 @@ impl Install {
         Ok(actual == expected)
     }
 
-    pub async fn download_to(path: &Path, url: &str, expected_hash: &str) -> Result<(), String> {
-        let bytes = Client::new()
+    /// Stream a planned download to disk, reporting the bytes received to `progress`
+    async fn download_with(
+        client: &Client,
+        download: &PlannedDownload,
+        progress: ProgressId,
+    ) -> Result<(), McpmError> {
+        let io = use_io();
+        let (path, url) = (&download.path, &download.url);
+        http::ensure_online(url)?;
+
+        let mut response = client
             .get(url)
             .send()
             .await
-            .map_err(|e| e.to_string())?
-            .bytes()
-            .await
-            .map_err(|e| e.to_string())?;
-        let mut file = fs::File::create(path).map_err(|e| e.to_string())?;
-        file.write_all(&bytes).map_err(|e| e.to_string())?;
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| McpmError::network(url, e))?;
 
+        let write_error = |e| McpmError::io(format!("Failed to write {}", path.display()), e);
+        let mut file = fs::File::create(path).map_err(write_error)?;
         let mut hasher = Sha512::new();
-        hasher.update(&bytes);
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| McpmError::network(url, e))?
+        {
+            file.write_all(&chunk).map_err(write_error)?;
+            hasher.update(&chunk);
+            io.progress_advance(progress, chunk.len() as u64);
+        }
+
         let actual_hash = format!("{:x}", hasher.finalize());
-        if actual_hash != expected_hash {
-            return Err(format!("Hash mismatch for {:?}", path));
+        if actual_hash != download.hash {
+            return Err(McpmError::HashMismatch { path: path.clone() });
         }
         Ok(())
     }
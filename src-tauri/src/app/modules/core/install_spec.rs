@@ -1,15 +1,18 @@
 #[cfg(test)]
 mod tests {
-    use std::{fs, path::PathBuf};
+    use std::{collections::HashMap, fs, path::Path};
     use tempfile::tempdir;
 
     use sha2::{Digest, Sha512};
     use tokio;
 
-    use crate::app::{
-        commands::Cli,
-        modules::{core::install::Install, io::init_io},
-        Config,
+    use crate::app::modules::{
+        core::{
+            install::{Install, InstallPlan, Placement},
+            test_workspace::workspace,
+        },
+        lock::models::{LockEntry, LockFile},
+        manifest::models::{Manifest, ModOptions, ModSpec, Side, VersionSpec, MANIFEST_SCHEMA},
     };
 
     // Verify that a basic install works
@@ -18,13 +21,8 @@ mod tests {
 
     #[tokio::test]
     async fn install_removes_old_versions_when_package_updates() {
-        // Arrange: temp workspace
-        let temp = tempdir().unwrap();
-        let root = temp.path().to_path_buf();
-        std::env::set_current_dir(&root).unwrap(); // Install reads mcpm.json/mcpm.lock from CWD
-
-        // Set config and IO once for the test
-        set_config(&root).await;
+        // Arrange: workspace with config and IO pointed into it
+        let (_workspace, root) = workspace().await;
 
         // First state: version 1.0.0
         make_manifest(&root, "1.0.0");
@@ -33,8 +31,9 @@ mod tests {
         seed_cache(&root, "1.0.0", v1_bytes);
 
         // Act 1: run install → copies v1 from cache to mods/
-        fs::create_dir_all(root.join("mods")).unwrap();
-        Install::run(false, false).await.expect("install v1 failed");
+        Install::run(false, false, &[])
+            .await
+            .expect("install v1 failed");
 
         let v1_path = root.join("mods").join("modrinth:testmod-1.0.0.jar");
         assert!(
@@ -49,7 +48,9 @@ mod tests {
         seed_cache(&root, "2.0.0", v2_bytes);
 
         // Act 2: run install again → installs v2 but does not remove v1 (current bug)
-        Install::run(false, false).await.expect("install v2 failed");
+        Install::run(false, false, &[])
+            .await
+            .expect("install v2 failed");
 
         let v2_path = root.join("mods").join("modrinth:testmod-2.0.0.jar");
         assert!(
@@ -62,27 +63,334 @@ mod tests {
             "Old version still present; expected cleanup to remove {}",
             v1_path.display()
         );
+
+        // The migrated manifest is written with the lock, ids included
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(root.join("mcpm.json")).unwrap()).unwrap();
+        assert_eq!(written["schema_version"], MANIFEST_SCHEMA);
+        assert_eq!(
+            written["mods"]["modrinth:testmod"]["id"],
+            "modrinth:testmod"
+        );
+    }
+
+    #[test]
+    fn plan_lists_changes_without_touching_disk() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let mods_dir = root.join("mods");
+        let cache_dir = root.join("cache");
+        fs::create_dir_all(&mods_dir).unwrap();
+
+        // v1 is installed, v2 is cached, and a stray file sits in mods/
+        fs::write(mods_dir.join("modrinth:testmod-1.0.0.jar"), b"jar-v1").unwrap();
+        fs::write(mods_dir.join("stray.jar"), b"stray").unwrap();
+        seed_cache(root, "2.0.0", b"jar-v2");
+
+        let before = lock_file(&[("modrinth:testmod", "1.0.0", b"jar-v1")]);
+        let after = lock_file(&[
+            ("modrinth:testmod", "2.0.0", b"jar-v2"),
+            ("modrinth:othermod", "0.1.0", b"other"),
+        ]);
+
+        let plan = InstallPlan::build(
+            &before,
+            after,
+            &HashMap::new(),
+            &mods_dir,
+            &cache_dir,
+            false,
+            false,
+        )
+        .expect("planning failed");
+
+        let changes: Vec<_> = plan
+            .lock_changes
+            .iter()
+            .map(|c| (c.key.as_str(), c.before.as_deref(), c.after.as_deref()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("modrinth:othermod", None, Some("0.1.0")),
+                ("modrinth:testmod", Some("1.0.0"), Some("2.0.0")),
+            ]
+        );
+
+        // Only the uncached mod is downloaded, both are copied from cache
+        assert_eq!(plan.downloads.len(), 1);
+        assert_eq!(plan.downloads[0].key, "modrinth:othermod");
+        assert_eq!(
+            plan.downloads[0].path,
+            cache_dir.join("modrinth:othermod-0.1.0.jar")
+        );
+        assert_eq!(plan.download_size(), 5);
+        assert_eq!(plan.copies.len(), 2);
+
+        assert_eq!(
+            plan.deletions,
+            vec![
+                mods_dir.join("modrinth:testmod-1.0.0.jar"),
+                mods_dir.join("stray.jar"),
+            ]
+        );
+
+        // Nothing was written
+        assert!(!cache_dir.join("modrinth:othermod-0.1.0.jar").exists());
+        assert!(!mods_dir.join("modrinth:testmod-2.0.0.jar").exists());
+        assert!(mods_dir.join("stray.jar").exists());
+    }
+
+    #[test]
+    fn plan_installs_disabled_mods_as_disabled_jars() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let mods_dir = root.join("mods");
+        let cache_dir = root.join("cache");
+        fs::create_dir_all(&mods_dir).unwrap();
+
+        // Currently enabled and installed
+        fs::write(mods_dir.join("modrinth:testmod-1.0.0.jar"), b"jar-v1").unwrap();
+        seed_cache(root, "1.0.0", b"jar-v1");
+
+        let lock = lock_file(&[("modrinth:testmod", "1.0.0", b"jar-v1")]);
+        let placements = HashMap::from([(
+            "modrinth:testmod".to_string(),
+            Placement {
+                disabled: true,
+                ..Default::default()
+            },
+        )]);
+
+        let plan = InstallPlan::build(
+            &lock,
+            lock.clone(),
+            &placements,
+            &mods_dir,
+            &cache_dir,
+            false,
+            false,
+        )
+        .expect("planning failed");
+
+        // Copied from cache under the disabled name, no network, enabled jar removed
+        assert!(plan.lock_changes.is_empty());
+        assert!(plan.downloads.is_empty());
+        assert_eq!(plan.copies.len(), 1);
+        assert_eq!(
+            plan.copies[0].to,
+            mods_dir.join("modrinth:testmod-1.0.0.jar.disabled")
+        );
+        assert_eq!(
+            plan.deletions,
+            vec![mods_dir.join("modrinth:testmod-1.0.0.jar")]
+        );
     }
 
-    async fn set_config(root: &PathBuf) {
-        // Point all paths into the temp workspace
-        let cli = Cli {
-            verbose: false,
-            quiet: true,
-            cache_dir: Some(root.join("cache").to_string_lossy().to_string()),
-            output_dir: Some(root.to_string_lossy().to_string()),
-            mods_dir: Some(root.join("mods").to_string_lossy().to_string()),
-            command: None,
+    #[test]
+    fn plan_honors_filenames_and_sides() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let mods_dir = root.join("mods");
+        let cache_dir = root.join("cache");
+        fs::create_dir_all(&mods_dir).unwrap();
+
+        // A server pack with a client-only mod that is already installed
+        let mut manifest = Manifest {
+            side: Side::Server,
+            ..Manifest::default()
+        };
+        let entry = |options| ModSpec {
+            version: VersionSpec::Exact("1.0.0".into()),
+            id: None,
+            options,
         };
-        Config::init(&cli);
-        init_io(crate::app::modules::io::traits::IOConfig {
-            verbose: false,
-            quiet: true,
-        })
-        .await;
+        manifest.mods.insert(
+            "modrinth:testmod".into(),
+            entry(ModOptions {
+                filename: Some("testmod.jar".into()),
+                ..Default::default()
+            }),
+        );
+        manifest.mods.insert(
+            "modrinth:clientmod".into(),
+            entry(ModOptions {
+                side: Some(Side::Client),
+                ..Default::default()
+            }),
+        );
+        fs::write(mods_dir.join("modrinth:clientmod-1.0.0.jar"), b"client").unwrap();
+
+        let placements = Install::placements(&manifest, &[]).unwrap();
+        let lock = lock_file(&[
+            ("modrinth:testmod", "1.0.0", b"jar-v1"),
+            ("modrinth:clientmod", "1.0.0", b"client"),
+        ]);
+        let plan = InstallPlan::build(
+            &lock,
+            lock.clone(),
+            &placements,
+            &mods_dir,
+            &cache_dir,
+            false,
+            false,
+        )
+        .expect("planning failed");
+
+        assert_eq!(plan.downloads.len(), 1);
+        assert_eq!(plan.copies.len(), 1);
+        assert_eq!(plan.copies[0].to, mods_dir.join("testmod.jar"));
+        assert_eq!(
+            plan.deletions,
+            vec![mods_dir.join("modrinth:clientmod-1.0.0.jar")]
+        );
+
+        // File names can't point outside the mods directory
+        manifest.mods.insert(
+            "modrinth:testmod".into(),
+            entry(ModOptions {
+                filename: Some("../testmod.jar".into()),
+                ..Default::default()
+            }),
+        );
+        assert!(Install::placements(&manifest, &[]).is_err());
+    }
+
+    #[test]
+    fn plan_leaves_optional_mods_out_unless_selected() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let mods_dir = root.join("mods");
+        let cache_dir = root.join("cache");
+
+        let mut manifest = Manifest::default();
+        manifest.mods.insert(
+            "modrinth:testmod".into(),
+            ModSpec {
+                version: VersionSpec::Exact("1.0.0".into()),
+                id: None,
+                options: ModOptions::default(),
+            },
+        );
+        manifest.mods.insert(
+            "modrinth:minimap".into(),
+            ModSpec {
+                version: VersionSpec::Exact("1.0.0".into()),
+                id: None,
+                options: ModOptions {
+                    optional: true,
+                    ..Default::default()
+                },
+            },
+        );
+        let lock = lock_file(&[
+            ("modrinth:testmod", "1.0.0", b"jar-v1"),
+            ("modrinth:minimap", "1.0.0", b"minimap"),
+        ]);
+        let plan = |with: &[String]| {
+            let selected = Install::pick(&manifest, &[], with).unwrap();
+            let placements = Install::placements(&manifest, &selected).unwrap();
+            InstallPlan::build(
+                &lock,
+                lock.clone(),
+                &placements,
+                &mods_dir,
+                &cache_dir,
+                false,
+                false,
+            )
+            .expect("planning failed")
+        };
+
+        let without = plan(&[]);
+        assert_eq!(without.downloads.len(), 1);
+        assert_eq!(without.downloads[0].key, "modrinth:testmod");
+        assert_eq!(without.left_out, vec!["modrinth:minimap"]);
+
+        // Selected by slug or key
+        for with in ["minimap", "modrinth:minimap"] {
+            let selected = plan(&[with.to_string()]);
+            assert_eq!(selected.downloads.len(), 2);
+            assert!(selected.left_out.is_empty());
+        }
+
+        assert!(Install::pick(&manifest, &[], &["minimpa".into()]).is_err());
+
+        // Saved picks add up with new ones, removed mods drop out
+        let saved = ["modrinth:minimap".to_string(), "modrinth:gone".to_string()];
+        assert_eq!(
+            Install::pick(&manifest, &saved, &["testmod".into()]).unwrap(),
+            vec!["modrinth:minimap", "modrinth:testmod"]
+        );
+    }
+
+    #[tokio::test]
+    async fn install_keeps_optional_mods_selected_earlier() {
+        let (_workspace, root) = workspace().await;
+        let manifest = serde_json::json!({
+            "name": "Pack",
+            "version": "1.0.0",
+            "side": "both",
+            "modloader": "fabric",
+            "minecraft_version": "1.21.7",
+            "default_provider": "modrinth",
+            "mods": {
+                "modrinth:testmod": "1.0.0",
+                "modrinth:minimap": { "version": "1.0.0", "optional": true }
+            }
+        });
+        write_json(&root.join("mcpm.json"), &manifest);
+        let lock = lock_file(&[
+            ("modrinth:testmod", "1.0.0", b"jar-v1" as &[u8]),
+            ("modrinth:minimap", "1.0.0", b"minimap"),
+        ]);
+        write_json(
+            &root.join("mcpm.lock"),
+            &serde_json::to_value(&lock).unwrap(),
+        );
+        seed_cache(&root, "1.0.0", b"jar-v1");
+        fs::write(root.join("cache/modrinth:minimap-1.0.0.jar"), b"minimap").unwrap();
+        let minimap = root.join("mods/modrinth:minimap-1.0.0.jar");
+
+        Install::run(false, false, &["minimap".into()])
+            .await
+            .expect("install with minimap failed");
+        assert!(minimap.exists());
+
+        // A plain install remembers the pick
+        let plan = Install::run(false, false, &[])
+            .await
+            .expect("plain install failed");
+        assert!(minimap.exists());
+        assert!(plan.left_out.is_empty());
+        assert_eq!(plan.selected, vec!["modrinth:minimap"]);
+    }
+
+    fn lock_file(mods: &[(&str, &str, &[u8])]) -> LockFile {
+        LockFile {
+            mods: mods
+                .iter()
+                .map(|(key, version, bytes)| {
+                    (
+                        key.to_string(),
+                        LockEntry {
+                            id: key.to_string(),
+                            version: version.to_string(),
+                            version_id: None,
+                            minecraft_versions: vec!["1.21.7".into()],
+                            url: "https://example.invalid/mod.jar".into(),
+                            hash: sha512_hex(bytes),
+                            size: Some(bytes.len() as u64),
+                            dependencies: vec![],
+                        },
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
     }
 
-    fn make_manifest(root: &PathBuf, version: &str) {
+    fn make_manifest(root: &Path, version: &str) {
         // Minimal, valid manifest that avoids network by using an exact version
         // and a provider-mapped key "modrinth:testmod".
         let manifest = serde_json::json!({
@@ -99,9 +407,10 @@ mod tests {
         write_json(&root.join("mcpm.json"), &manifest);
     }
 
-    fn make_lock(root: &PathBuf, version: &str, bytes: &[u8]) {
+    fn make_lock(root: &Path, version: &str, bytes: &[u8]) {
         let hash = sha512_hex(bytes);
         let lock = serde_json::json!({
+            "loader": { "name": "fabric", "version": "0.16.14", "minecraft_version": "1.21.7" },
             "mods": {
                 "modrinth:testmod": {
                     "id": "modrinth:testmod",
@@ -115,14 +424,14 @@ mod tests {
         write_json(&root.join("mcpm.lock"), &lock);
     }
 
-    fn seed_cache(root: &PathBuf, version: &str, bytes: &[u8]) {
+    fn seed_cache(root: &Path, version: &str, bytes: &[u8]) {
         let cache_dir = root.join("cache");
         fs::create_dir_all(&cache_dir).unwrap();
         let fname = format!("modrinth:testmod-{}.jar", version);
         fs::write(cache_dir.join(fname), bytes).unwrap();
     }
 
-    fn write_json(path: &PathBuf, content: &serde_json::Value) {
+    fn write_json(path: &Path, content: &serde_json::Value) {
         fs::write(path, serde_json::to_string_pretty(content).unwrap()).unwrap();
     }
 
@@ -1,11 +1,45 @@
-use clap::Args;
+use serde::Serialize;
 
-#[derive(Args)]
-pub struct List {}
+use crate::app::{
+    modules::{core::ops::manager::ModManager, manifest::models::VersionSpec},
+    McpmError,
+};
+
+/// A manifest entry together with what the lock resolved it to
+#[derive(Debug, Clone, Serialize)]
+pub struct ListEntry {
+    pub key: String,
+    pub spec: VersionSpec,
+    pub locked: Option<String>,
+    pub disabled: bool,
+    pub optional: bool,
+    pub pinned: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+pub struct List;
 
 impl List {
-    pub fn run() {
-        println!("Listing installed mods...");
-        // TODO: real list logic here
+    pub async fn run() -> Result<Vec<ListEntry>, McpmError> {
+        let manager = ModManager::load().await?;
+
+        let mut entries: Vec<ListEntry> = manager
+            .manifest
+            .mods_as_entries()
+            .into_iter()
+            .map(|m| ListEntry {
+                key: m.to_key(),
+                locked: manager.lock_service.get_version(&m),
+                spec: m.version,
+                disabled: m.options.disabled,
+                optional: m.options.optional,
+                pinned: m.options.pinned,
+                notes: m.options.notes,
+            })
+            .collect();
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(entries)
     }
 }
@@ -0,0 +1,93 @@
+use serde::Serialize;
+
+use crate::app::{
+    helpers::as_str,
+    modules::{
+        core::{
+            check_mc::{CheckMc, CheckMcReport},
+            ops::manager::ModManager,
+        },
+        io::use_io,
+        loaders::models::LockedLoader,
+        manifest::models::ModLoader,
+        minecraft::MinecraftService,
+    },
+    McpmError,
+};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrateResult {
+    pub from: String,
+    /// Loader version locked for the target
+    pub loader: Option<LockedLoader>,
+    /// The manifest's `loader_version`, dropped because the target has no build of it
+    pub dropped_loader_version: Option<String>,
+    #[serde(flatten)]
+    pub report: CheckMcReport,
+}
+
+pub struct Migrate;
+
+impl Migrate {
+    /// Move the manifest to another Minecraft version and loader and re-resolve the lock.
+    /// Nothing is written unless every entry has a compatible version.
+    /// Snapshots are only accepted with `snapshot`.
+    pub async fn run(
+        minecraft_version: &str,
+        loader: Option<ModLoader>,
+        snapshot: bool,
+    ) -> Result<MigrateResult, McpmError> {
+        MinecraftService::validate(minecraft_version, snapshot).await?;
+        Self::run_with(ModManager::load().await?, minecraft_version, loader).await
+    }
+
+    /// [`Self::run`] on an already loaded manager, once the Minecraft version is validated
+    pub async fn run_with(
+        mut manager: ModManager,
+        minecraft_version: &str,
+        loader: Option<ModLoader>,
+    ) -> Result<MigrateResult, McpmError> {
+        let target = CheckMc::target(&manager.manifest, minecraft_version, loader);
+        let (report, resolved) = CheckMc::check(&manager, &target).await?;
+
+        let incompatible = report.incompatible();
+        if !incompatible.is_empty() {
+            return Err(McpmError::NoCompatibleVersion {
+                slug: incompatible.join("', '"),
+                detail: format!(
+                    "Minecraft {} + {:?}, see `mcpm check-mc {}`",
+                    target.minecraft_version, target.modloader, target.minecraft_version
+                ),
+            });
+        }
+
+        let from = std::mem::replace(&mut manager.manifest, target).minecraft_version;
+        manager.lock_service.lock.mods = resolved.lock.mods;
+        let mut dropped_loader_version = None;
+        if let Err(e) = manager.refresh_loader(false).await {
+            // NeoForge and Forge versions only exist for one Minecraft version
+            let McpmError::NoCompatibleVersion { .. } = e else {
+                return Err(e);
+            };
+            dropped_loader_version = manager.manifest.loader_version.take();
+            let Some(dropped) = &dropped_loader_version else {
+                return Err(e);
+            };
+            manager.refresh_loader(false).await?;
+            use_io().warn(&format!(
+                "{} {} has no build for Minecraft {}, loader_version was removed from the manifest",
+                as_str(&manager.manifest.modloader),
+                dropped,
+                manager.manifest.minecraft_version
+            ));
+        }
+        manager.save_all()?;
+
+        Ok(MigrateResult {
+            from,
+            loader: manager.lock_service.lock.loader.clone(),
+            dropped_loader_version,
+            report,
+        })
+    }
+}
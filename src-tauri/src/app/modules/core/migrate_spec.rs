@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use serde_json::json;
+
+    use crate::app::{
+        modules::{
+            core::{
+                migrate::Migrate,
+                test_workspace::{manager, workspace, FakeRepository},
+            },
+            repositories::models::VersionResult,
+        },
+        McpmError,
+    };
+
+    /// Sodium and lithium locked for 1.21.7, only sodium has a build for 1.21.8
+    fn make_pack(root: &Path) {
+        let manifest = json!({
+            "name": "Pack",
+            "minecraft_version": "1.21.7",
+            "modloader": "fabric",
+            "mods": {
+                "modrinth:sodium": { "version": "^0.6", "id": "AANobbMI" },
+                "modrinth:lithium": { "version": "^0.14", "id": "gvQqBUqZ" }
+            }
+        });
+        let locked = |id: &str, version: &str| {
+            json!({
+                "id": id,
+                "version": version,
+                "minecraft_versions": ["1.21.7"],
+                "url": "https://example.invalid/mod.jar",
+                "hash": "00"
+            })
+        };
+        let lock = json!({
+            "mods": {
+                "modrinth:sodium": locked("AANobbMI", "0.6.12"),
+                "modrinth:lithium": locked("gvQqBUqZ", "0.14.0")
+            }
+        });
+        fs::write(root.join("mcpm.json"), manifest.to_string()).unwrap();
+        fs::write(root.join("mcpm.lock"), lock.to_string()).unwrap();
+    }
+
+    fn repository(lithium_on_1_21_8: bool) -> FakeRepository {
+        let version = |id: &str, v: &str, mc: &str| VersionResult {
+            mod_id: id.into(),
+            version_id: format!("{}-{}", id, v),
+            version: v.into(),
+            minecraft_versions: vec![mc.into()],
+            url: "https://example.invalid/mod.jar".into(),
+            hash: "00".into(),
+            ..Default::default()
+        };
+        let mut versions = vec![
+            version("AANobbMI", "0.6.13", "1.21.8"),
+            version("AANobbMI", "0.6.12", "1.21.7"),
+            version("gvQqBUqZ", "0.14.0", "1.21.7"),
+        ];
+        if lithium_on_1_21_8 {
+            versions.push(version("gvQqBUqZ", "0.14.1", "1.21.8"));
+        }
+        FakeRepository {
+            versions,
+            ..Default::default()
+        }
+    }
+
+    fn written(root: &Path, file: &str) -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(root.join(file)).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn compatible_packs_are_moved() {
+        let (_workspace, root) = workspace().await;
+        make_pack(&root);
+
+        let result = Migrate::run_with(manager(repository(true)), "1.21.8", None)
+            .await
+            .unwrap();
+        assert_eq!(result.from, "1.21.7");
+        assert!(result.dropped_loader_version.is_none());
+
+        assert_eq!(written(&root, "mcpm.json")["minecraft_version"], "1.21.8");
+        let locked = &written(&root, "mcpm.lock")["mods"];
+        assert_eq!(locked["modrinth:sodium"]["version"], "0.6.13");
+        assert_eq!(locked["modrinth:lithium"]["version"], "0.14.1");
+    }
+
+    #[tokio::test]
+    async fn nothing_is_written_while_a_mod_is_incompatible() {
+        let (_workspace, root) = workspace().await;
+        make_pack(&root);
+        let manifest = fs::read_to_string(root.join("mcpm.json")).unwrap();
+        let lock = fs::read_to_string(root.join("mcpm.lock")).unwrap();
+
+        let err = Migrate::run_with(manager(repository(false)), "1.21.8", None)
+            .await
+            .unwrap_err();
+        let McpmError::NoCompatibleVersion { slug, .. } = err else {
+            panic!("expected NoCompatibleVersion, got {err:?}");
+        };
+        assert_eq!(slug, "modrinth:lithium");
+        assert_eq!(
+            fs::read_to_string(root.join("mcpm.json")).unwrap(),
+            manifest
+        );
+        assert_eq!(fs::read_to_string(root.join("mcpm.lock")).unwrap(), lock);
+    }
+}
@@ -1,12 +1,38 @@
 pub mod add;
+pub mod bisect;
+pub mod check_mc;
+pub mod config;
+pub mod convert;
+pub mod doctor;
+pub mod history;
 pub mod init;
 pub mod install;
 pub mod list;
+pub mod migrate;
 mod ops;
 pub mod outdated;
+pub mod pin;
 pub mod remove;
+pub mod rollback;
 pub mod search;
+pub mod toggle;
 pub mod upgrade;
 
+#[cfg(test)]
+mod add_spec;
+#[cfg(test)]
+mod bisect_spec;
+#[cfg(test)]
+mod check_mc_spec;
+#[cfg(test)]
+mod convert_spec;
+#[cfg(test)]
+mod doctor_spec;
 #[cfg(test)]
 mod install_spec;
+#[cfg(test)]
+mod migrate_spec;
+#[cfg(test)]
+mod pin_spec;
+#[cfg(test)]
+mod test_workspace;
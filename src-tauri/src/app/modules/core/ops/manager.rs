@@ -1,9 +1,13 @@
-use crate::app::modules::{
-    lock::services::LockService,
-    manifest::{models::ModEntry, ManifestService},
-    repositories::{models::VersionResult, modrinth::ModrinthRepository, RepositoryService},
+use crate::app::{
+    modules::{
+        loaders::LoaderService,
+        lock::services::LockService,
+        manifest::{models::ModEntry, ManifestService},
+        minecraft::MinecraftService,
+        repositories::{models::VersionResult, modrinth::ModrinthRepository, RepositoryService},
+    },
+    McpmError,
 };
-use std::io::Result;
 
 pub struct ModManager {
     pub manifest_service: ManifestService,
@@ -13,12 +17,12 @@ pub struct ModManager {
 }
 
 impl ModManager {
-    pub async fn load() -> Result<Self> {
+    pub async fn load() -> Result<Self, McpmError> {
         let manifest_service = ManifestService::new();
         let manifest = manifest_service.load()?;
         let repo_service =
             RepositoryService::new().with_provider("modrinth", Box::new(ModrinthRepository::new()));
-        let lock_service = LockService::load();
+        let lock_service = LockService::load()?;
 
         Ok(Self {
             manifest_service,
@@ -28,14 +32,29 @@ impl ModManager {
         })
     }
 
+    /// Check the Minecraft version once before resolving `entries`, a mistyped
+    /// one would fail every query. Nothing is fetched when none needs resolving.
+    pub async fn validate_minecraft(
+        &self,
+        entries: &[ModEntry],
+        upgrade: bool,
+    ) -> Result<(), McpmError> {
+        if entries
+            .iter()
+            .any(|m| self.lock_service.needs_update(m, &self.manifest, upgrade))
+        {
+            MinecraftService::validate(&self.manifest.minecraft_version, true).await?;
+        }
+        Ok(())
+    }
+
     pub async fn refresh_mod(
         &mut self,
         entry: &ModEntry,
         available: Option<&[VersionResult]>,
         upgrade: bool,
-    ) -> Result<()> {
-        let success = self
-            .lock_service
+    ) -> Result<(), McpmError> {
+        self.lock_service
             .update_entry(
                 entry,
                 &self.manifest,
@@ -43,19 +62,33 @@ impl ModManager {
                 available,
                 upgrade,
             )
-            .await;
-        if !success {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "update failed",
-            ));
+            .await
+    }
+
+    pub async fn refresh_loader(&mut self, upgrade: bool) -> Result<(), McpmError> {
+        self.lock_service
+            .update_loader(&self.manifest, &LoaderService::new(), upgrade)
+            .await
+    }
+
+    /// Record the project id of entries that have none yet, from their lock entry
+    pub fn record_ids(&mut self) {
+        let lock = &self.lock_service.lock;
+        for (key, spec) in self.manifest.mods.iter_mut() {
+            if spec.id.is_none() {
+                spec.id = lock.mods.get(key).map(|e| e.id.clone());
+            }
         }
-        Ok(())
     }
 
-    pub fn save_all(&self) -> Result<()> {
-        self.manifest_service.save(&self.manifest)?;
-        self.lock_service.save()?;
+    pub fn save_all(&mut self) -> Result<(), McpmError> {
+        self.record_ids();
+        self.manifest_service
+            .save(&self.manifest)
+            .map_err(|e| McpmError::io("Failed to save manifest", e))?;
+        self.lock_service
+            .save()
+            .map_err(|e| McpmError::io("Failed to save lockfile", e))?;
         Ok(())
     }
 }
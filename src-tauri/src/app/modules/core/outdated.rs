@@ -1,34 +1,51 @@
 use futures::stream::{FuturesUnordered, StreamExt};
+use serde::Serialize;
 
 use crate::app::{
     helpers::{as_str, semver::resolve_version},
     modules::{
         core::ops::manager::ModManager,
-        manifest::models::{ModEntry, VersionSpec},
+        loaders::LoaderService,
+        manifest::models::{ModEntry, ModLoader, VersionSpec},
+        minecraft::MinecraftService,
     },
+    McpmError,
 };
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OutdatedEntry {
     pub key: String,
     pub current: String,
-    pub wanted: Option<String>, // latest within spec
-    pub latest: Option<String>, // latest overall
+    pub wanted: Option<String>,     // latest within spec
+    pub latest: Option<String>,     // latest on the allowed channel, stable by default
+    pub latest_any: Option<String>, // latest on any channel
+    pub pinned: bool,
+    pub pin_reason: Option<String>,
 }
 
-#[derive(Debug, Clone, Default)]
+/// A locked loader version with a newer stable one available
+#[derive(Debug, Clone, Serialize)]
+pub struct OutdatedLoader {
+    pub name: ModLoader,
+    pub current: String,
+    pub latest: String,
+    /// Set by the manifest's `loader_version`
+    pub pinned: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct OutdatedResult {
     pub outdated: Vec<OutdatedEntry>,
     pub total_checked: usize,
+    /// Only checked when no mods are named
+    pub loader: Option<OutdatedLoader>,
 }
 
 pub struct Outdated;
 
 impl Outdated {
-    pub async fn run(mods: Vec<String>) -> Result<OutdatedResult, String> {
-        let mut manager = ModManager::load()
-            .await
-            .map_err(|e| format!("Failed to initialize ModManager: {}", e))?;
+    pub async fn run(mods: Vec<String>) -> Result<OutdatedResult, McpmError> {
+        let manager = ModManager::load().await?;
 
         let to_check: Vec<_> = if mods.is_empty() {
             manager.manifest.mods_as_entries()
@@ -42,13 +59,20 @@ impl Outdated {
         };
 
         if to_check.is_empty() {
-            return Err("No matching mods found to check for updates".into());
+            return Err(McpmError::NotFound(
+                "Mods to check matching the query".into(),
+            ));
         }
 
+        MinecraftService::validate(&manager.manifest.minecraft_version, true).await?;
         let mut result = OutdatedResult {
             outdated: vec![],
             total_checked: to_check.len(),
+            loader: None,
         };
+        if mods.is_empty() {
+            result.loader = Self::check_loader(&manager).await?;
+        }
 
         // Concurrently check each mod for updates
         let mut checks = to_check
@@ -61,7 +85,7 @@ impl Outdated {
             .collect::<FuturesUnordered<_>>();
 
         while let Some(entry) = checks.next().await {
-            if let Some(e) = entry {
+            if let Some(e) = entry? {
                 result.outdated.push(e);
             }
         }
@@ -71,42 +95,82 @@ impl Outdated {
         Ok(result)
     }
 
-    async fn check_mod(manager: &ModManager, m: ModEntry) -> Option<OutdatedEntry> {
+    async fn check_loader(manager: &ModManager) -> Result<Option<OutdatedLoader>, McpmError> {
+        let manifest = &manager.manifest;
+        let Some(locked) = &manager.lock_service.lock.loader else {
+            return Ok(None);
+        };
+        if locked.name != manifest.modloader {
+            return Ok(None);
+        }
+
+        let versions = LoaderService::new()
+            .versions(locked.name, &locked.minecraft_version)
+            .await?;
+        let Some(latest) = versions.iter().find(|v| v.stable) else {
+            return Ok(None);
+        };
+
+        Ok((latest.version != locked.version).then(|| OutdatedLoader {
+            name: locked.name,
+            current: locked.version.clone(),
+            latest: latest.version.clone(),
+            pinned: manifest.loader_version.is_some(),
+        }))
+    }
+
+    async fn check_mod(
+        manager: &ModManager,
+        m: ModEntry,
+    ) -> Result<Option<OutdatedEntry>, McpmError> {
         let key = m.to_key();
-        let lock_entry = manager.lock_service.lock.mods.get(&key)?;
+        let Some(lock_entry) = manager.lock_service.lock.mods.get(&key) else {
+            return Ok(None);
+        };
 
         let versions = manager
             .repo_service
             .get_versions(
-                &key,
-                &[manager.manifest.minecraft_version.clone()],
+                &manager.lock_service.project_ref(&m),
+                std::slice::from_ref(&manager.manifest.minecraft_version),
                 &[as_str(&manager.manifest.modloader)],
             )
-            .await;
+            .await?;
         if versions.is_empty() {
-            return None;
+            return Ok(None);
         }
 
-        let latest = versions.first().map(|v| v.version.clone());
-        let wanted = match &m.version {
-            VersionSpec::Range(spec) => {
-                resolve_version(spec.as_str(), &versions).map(|v| v.version.clone())
+        // Newest first, as the provider lists them
+        let channel = manager.manifest.channel_for(&m);
+        let latest_any = versions.first().map(|v| v.version.clone());
+        let latest = versions
+            .iter()
+            .find(|v| v.version_type <= channel)
+            .map(|v| v.version.clone());
+        let wanted = match (&m.options.pinned, &m.version) {
+            _ if m.options.version_id.is_some() => Some(lock_entry.version.clone()),
+            (Some(pinned), _) => Some(pinned.clone()),
+            (None, VersionSpec::Range(spec)) => {
+                resolve_version(spec.as_str(), &versions, channel).map(|v| v.version.clone())
             }
-            VersionSpec::Exact(_) => Some(lock_entry.version.clone()),
+            (None, VersionSpec::Exact(_)) => Some(lock_entry.version.clone()),
         };
 
         let current = &lock_entry.version;
-        if wanted.as_ref().map_or(false, |w| w != current)
-            || latest.as_ref().map_or(false, |l| l != current)
+        if wanted.as_ref().is_some_and(|w| w != current)
+            || latest.as_ref().is_some_and(|l| l != current)
         {
-            Some(OutdatedEntry {
+            Ok(Some(OutdatedEntry {
                 key,
                 current: current.clone(),
                 wanted,
                 latest,
-            })
+                latest_any,
+                pinned: m.is_frozen(),
+                pin_reason: m.options.pin_reason.clone(),
+            }))
         } else {
-            None
+            Ok(None)
         }
     }
 }
@@ -0,0 +1,86 @@
+use crate::app::{
+    modules::{core::ops::manager::ModManager, manifest::models::Provider},
+    McpmError,
+};
+
+pub struct Pin;
+
+impl Pin {
+    /// Freeze an entry at `version`, or at its locked version if none is given.
+    /// Returns the pinned version.
+    pub async fn pin(
+        slug: String,
+        provider: Option<Provider>,
+        version: Option<String>,
+        reason: Option<String>,
+    ) -> Result<String, McpmError> {
+        Self::pin_with(ModManager::load().await?, slug, provider, version, reason).await
+    }
+
+    /// [`Self::pin`] on an already loaded manager
+    pub async fn pin_with(
+        mut manager: ModManager,
+        slug: String,
+        provider: Option<Provider>,
+        version: Option<String>,
+        reason: Option<String>,
+    ) -> Result<String, McpmError> {
+        let provider = provider.unwrap_or(manager.manifest.default_provider.clone());
+        let mut entry = manager
+            .manifest
+            .mods_as_entries()
+            .into_iter()
+            .find(|m| m.to_key() == format!("{}:{}", provider, slug))
+            .ok_or_else(|| McpmError::NotFound(format!("Mod '{}' in manifest", slug)))?;
+
+        let version = version
+            .or_else(|| manager.lock_service.get_version(&entry))
+            .ok_or_else(|| {
+                McpmError::InvalidInput(format!(
+                    "Mod '{}' is not locked yet, specify the version to pin",
+                    slug
+                ))
+            })?;
+
+        entry.options.pinned = Some(version.clone());
+        entry.options.pin_reason = reason;
+        manager.manifest.insert_mod_entry(&entry);
+
+        // Moves the lock if pinning to a different version than the locked one
+        manager
+            .validate_minecraft(std::slice::from_ref(&entry), false)
+            .await?;
+        manager.refresh_mod(&entry, None, false).await?;
+
+        manager.save_all()?;
+
+        Ok(version)
+    }
+
+    /// Let a pinned entry follow its version spec again.
+    /// Fails on entries that aren't pinned, like on unknown ones.
+    pub async fn unpin(slug: String, provider: Option<Provider>) -> Result<(), McpmError> {
+        Self::unpin_with(ModManager::load().await?, slug, provider)
+    }
+
+    /// [`Self::unpin`] on an already loaded manager
+    pub fn unpin_with(
+        mut manager: ModManager,
+        slug: String,
+        provider: Option<Provider>,
+    ) -> Result<(), McpmError> {
+        let provider = provider.unwrap_or(manager.manifest.default_provider.clone());
+        let spec = manager.manifest.get_mod_mut(&provider, &slug)?;
+
+        if spec.options.pinned.is_none() {
+            return Err(McpmError::InvalidInput(format!(
+                "Mod '{}' is not pinned",
+                slug
+            )));
+        }
+
+        spec.options.pinned = None;
+        spec.options.pin_reason = None;
+        manager.save_all()
+    }
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use serde_json::json;
+
+    use crate::app::{
+        modules::{
+            core::{
+                pin::Pin,
+                test_workspace::{manager, workspace, FakeRepository},
+                upgrade::Upgrade,
+            },
+            repositories::models::VersionResult,
+        },
+        McpmError,
+    };
+
+    /// Sodium locked at 0.6.1 with 0.6.13 out, lithium held by its version id
+    fn make_pack(root: &Path, sodium: serde_json::Value) {
+        let manifest = json!({
+            "name": "Pack",
+            "minecraft_version": "1.21.7",
+            "modloader": "fabric",
+            "mods": {
+                "modrinth:sodium": sodium,
+                "modrinth:lithium": { "version": "^0.14", "version_id": "gvQqBUqZ-0.14.0" }
+            }
+        });
+        let lock = json!({
+            "mods": {
+                "modrinth:sodium": locked("AANobbMI", "0.6.1"),
+                "modrinth:lithium": locked("gvQqBUqZ", "0.14.0")
+            }
+        });
+        fs::write(root.join("mcpm.json"), manifest.to_string()).unwrap();
+        fs::write(root.join("mcpm.lock"), lock.to_string()).unwrap();
+    }
+
+    fn locked(id: &str, version: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "version": version,
+            "version_id": format!("{}-{}", id, version),
+            "minecraft_versions": ["1.21.7"],
+            "url": "https://example.invalid/mod.jar",
+            "hash": "00"
+        })
+    }
+
+    fn repository() -> FakeRepository {
+        let version = |id: &str, v: &str| VersionResult {
+            mod_id: id.into(),
+            version_id: format!("{}-{}", id, v),
+            version: v.into(),
+            minecraft_versions: vec!["1.21.7".into()],
+            url: "https://example.invalid/mod.jar".into(),
+            hash: "00".into(),
+            ..Default::default()
+        };
+        FakeRepository {
+            versions: vec![
+                version("AANobbMI", "0.6.13"),
+                version("AANobbMI", "0.6.1"),
+                version("gvQqBUqZ", "0.14.2"),
+                version("gvQqBUqZ", "0.14.0"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn written(root: &Path, file: &str) -> serde_json::Value {
+        serde_json::from_str(&fs::read_to_string(root.join(file)).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn pin_defaults_to_the_locked_version() {
+        let (_workspace, root) = workspace().await;
+        make_pack(&root, json!("^0.6"));
+
+        let version = Pin::pin_with(
+            manager(repository()),
+            "sodium".into(),
+            None,
+            None,
+            Some("crashes with iris".into()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(version, "0.6.1");
+        let sodium = &written(&root, "mcpm.json")["mods"]["modrinth:sodium"];
+        assert_eq!(sodium["pinned"], "0.6.1");
+        assert_eq!(sodium["pin_reason"], "crashes with iris");
+        assert_eq!(
+            written(&root, "mcpm.lock")["mods"]["modrinth:sodium"]["version"],
+            "0.6.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn upgrade_skips_pinned_mods_until_unpinned() {
+        let (_workspace, root) = workspace().await;
+        make_pack(&root, json!({ "version": "^0.6", "pinned": "0.6.1" }));
+        let mods = ["sodium".to_string(), "lithium".to_string()];
+
+        let result = Upgrade::run_with(manager(repository()), &mods)
+            .await
+            .unwrap();
+        assert!(result.upgraded.is_empty());
+        assert_eq!(result.pinned, vec!["modrinth:sodium"]);
+        assert_eq!(result.held, vec!["modrinth:lithium"]);
+
+        Pin::unpin_with(manager(repository()), "sodium".into(), None).unwrap();
+        assert!(written(&root, "mcpm.json")["mods"]["modrinth:sodium"]
+            .get("pinned")
+            .is_none());
+
+        let result = Upgrade::run_with(manager(repository()), &mods)
+            .await
+            .unwrap();
+        assert_eq!(result.upgraded.len(), 1);
+        assert_eq!(result.upgraded[0].key, "modrinth:sodium");
+        assert_eq!(result.upgraded[0].after.as_deref(), Some("0.6.13"));
+        assert!(result.pinned.is_empty());
+
+        // Unpinning again is an error, like unpinning an unknown mod
+        let err = Pin::unpin_with(manager(repository()), "sodium".into(), None).unwrap_err();
+        assert!(matches!(err, McpmError::InvalidInput(_)));
+        assert!(Pin::unpin_with(manager(repository()), "iris".into(), None).is_err());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::app::{
+    modules::{
+        core::install::Install,
+        lock::services::LockService,
+        manifest::ManifestService,
+        snapshot::{models::Snapshot, SnapshotService},
+    },
+    Config, McpmError,
+};
+
+pub struct Rollback;
+
+impl Rollback {
+    /// Restore the manifest, lock and mods of a snapshot (latest if `to` is None)
+    /// using only cached files.
+    pub async fn run(to: Option<String>) -> Result<Snapshot, McpmError> {
+        let service = SnapshotService::new();
+
+        let snapshot = match to {
+            Some(id) => service
+                .get(&id)
+                .ok_or_else(|| McpmError::NotFound(format!("Snapshot '{}'", id)))?,
+            None => service
+                .list()
+                .into_iter()
+                .next()
+                .ok_or_else(|| McpmError::NotFound("Snapshot to roll back to".into()))?,
+        };
+
+        let manifest = ManifestService::new();
+        let manifest_path = manifest.path();
+        let lock_path = &LockService::path();
+        let cache_dir = &Config::get().cache_dir;
+
+        // Keep the current state so the rollback itself can be undone
+        service
+            .capture(
+                manifest_path,
+                lock_path,
+                cache_dir,
+                &format!("before rollback to {}", snapshot.id),
+            )
+            .map_err(|e| McpmError::io("Failed to record snapshot", e))?;
+
+        service.restore(&snapshot, manifest_path, lock_path, cache_dir)?;
+
+        let plan = Install::plan(false, false, &[]).await?;
+        Install::apply(&plan).await?;
+
+        Ok(snapshot)
+    }
+}
@@ -1,16 +1,15 @@
-use crate::app::modules::repositories::{modrinth::ModrinthRepository, RepositoryService};
+use crate::app::{
+    modules::repositories::{models::ModResult, modrinth::ModrinthRepository, RepositoryService},
+    McpmError,
+};
 
 pub struct Search;
 
 impl Search {
-    pub async fn run(query: String, page: Option<usize>) {
+    pub async fn run(query: String, page: Option<usize>) -> Result<Vec<ModResult>, McpmError> {
         let service =
             RepositoryService::new().with_provider("modrinth", Box::new(ModrinthRepository::new()));
 
-        let results = service.search(&query, page.unwrap_or(0)).await;
-
-        for r in results {
-            println!("[{}] {} - {}", r.source, r.name, r.url);
-        }
+        service.search(&query, page.unwrap_or(0)).await
     }
 }
@@ -0,0 +1,121 @@
+//! A project directory for specs that go through the global [`Config`].
+//! Config is set once per test binary, so those specs share one directory
+//! and take turns on it. It is offline, providers are faked.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use async_trait::async_trait;
+use tokio::sync::{Mutex, MutexGuard};
+
+use crate::app::{
+    commands::Cli,
+    modules::{
+        core::ops::manager::ModManager,
+        io::{init_io, traits::IOConfig},
+        lock::services::LockService,
+        manifest::ManifestService,
+        repositories::{
+            models::{ModResult, VersionResult},
+            IRepository, RepositoryService,
+        },
+    },
+    Config, McpmError,
+};
+
+static WORKSPACE: Mutex<()> = Mutex::const_new(());
+
+/// Lock the workspace and empty it. The manifest is `mcpm.json` in the returned
+/// directory, mods go to `mods/` and the cache to `cache/`.
+pub(crate) async fn workspace() -> (MutexGuard<'static, ()>, PathBuf) {
+    let guard = WORKSPACE.lock().await;
+    let root = env::temp_dir().join(format!("mcpm-spec-{}", process::id()));
+    if root.exists() {
+        fs::remove_dir_all(&root).unwrap();
+    }
+    fs::create_dir_all(root.join("mods")).unwrap();
+    set_config(&root).await;
+    (guard, root)
+}
+
+async fn set_config(root: &Path) {
+    // Point all paths into the workspace
+    let cli = Cli {
+        verbose: false,
+        quiet: true,
+        manifest: Some(root.join("mcpm.json").to_string_lossy().to_string()),
+        cache_dir: Some(root.join("cache").to_string_lossy().to_string()),
+        output_dir: Some(root.to_string_lossy().to_string()),
+        mods_dir: Some(root.join("mods").to_string_lossy().to_string()),
+        offline: true,
+        output: Default::default(),
+        log_format: Default::default(),
+        non_interactive: false,
+        yes: false,
+        command: None,
+    };
+    Config::init(&cli).unwrap();
+    init_io(IOConfig {
+        verbose: false,
+        quiet: true,
+        format: Default::default(),
+        assume_yes: false,
+    })
+    .await;
+}
+
+/// Provider answering from memory: `projects` by slug or id, `versions` by project id
+/// and Minecraft version
+#[derive(Default)]
+pub(crate) struct FakeRepository {
+    pub projects: Vec<ModResult>,
+    pub versions: Vec<VersionResult>,
+}
+
+#[async_trait]
+impl IRepository for FakeRepository {
+    async fn search(&self, _: &str, _: usize) -> Result<Vec<ModResult>, McpmError> {
+        Ok(vec![])
+    }
+
+    async fn find(&self, slug: &str) -> Result<Option<ModResult>, McpmError> {
+        Ok(self
+            .projects
+            .iter()
+            .find(|p| p.slug == slug || p.id == slug)
+            .cloned())
+    }
+
+    async fn get_versions(
+        &self,
+        project_id: &str,
+        game_versions: &[String],
+        _: &[String],
+    ) -> Result<Vec<VersionResult>, McpmError> {
+        Ok(self
+            .versions
+            .iter()
+            .filter(|v| v.mod_id == project_id)
+            .filter(|v| {
+                game_versions
+                    .iter()
+                    .any(|g| v.minecraft_versions.contains(g))
+            })
+            .cloned()
+            .collect())
+    }
+}
+
+/// A manager on the workspace's manifest and lock, with `repository` as modrinth
+pub(crate) fn manager(repository: FakeRepository) -> ModManager {
+    let manifest_service = ManifestService::new();
+    ModManager {
+        manifest: manifest_service.load().unwrap(),
+        manifest_service,
+        lock_service: LockService::load().unwrap(),
+        repo_service: RepositoryService::new().with_provider("modrinth", Box::new(repository)),
+    }
+}
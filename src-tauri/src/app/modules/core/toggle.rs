@@ -0,0 +1,31 @@
+use crate::app::{
+    modules::{core::ops::manager::ModManager, manifest::models::Provider},
+    McpmError,
+};
+
+pub struct Toggle;
+
+impl Toggle {
+    /// Mark a manifest entry as disabled or enabled. The lock entry is kept,
+    /// `install` decides how the jar ends up in the mods directory.
+    pub async fn run(
+        slug: String,
+        provider: Option<Provider>,
+        disabled: bool,
+    ) -> Result<Option<String>, McpmError> {
+        let mut manager = ModManager::load().await?;
+
+        let provider = provider.unwrap_or(manager.manifest.default_provider.clone());
+        let spec = manager.manifest.get_mod_mut(&provider, &slug)?;
+
+        if spec.options.disabled == disabled {
+            let state = if disabled { "disabled" } else { "enabled" };
+            return Ok(Some(format!("Mod '{}' is already {}", slug, state)));
+        }
+
+        spec.options.disabled = disabled;
+        manager.save_all()?;
+
+        Ok(None)
+    }
+}
@@ -1,18 +1,70 @@
-use crate::app::modules::core::ops::manager::ModManager;
+use crate::app::helpers::as_str;
+use crate::app::modules::{
+    core::{
+        install::{Install, InstallPlan, LockChange},
+        ops::manager::ModManager,
+    },
+    lock::models::LockFile,
+};
+use crate::app::McpmError;
+use serde::Serialize;
 use std::collections::HashMap;
 
+#[derive(Debug, Clone, Serialize)]
 pub struct UpgradeResult {
-    pub upgraded: Vec<(String, Option<String>, Option<String>)>, // key, before, after
+    pub upgraded: Vec<LockChange>,
     pub unchanged: usize,
+    pub pinned: Vec<String>, // keys skipped because they are pinned
+    pub held: Vec<String>,   // keys skipped because a `version_id` selects their version
+}
+
+/// An upgrade together with the install that followed it
+#[derive(Debug, Clone, Serialize)]
+pub struct UpgradeOutput {
+    #[serde(flatten)]
+    pub result: UpgradeResult,
+    /// None when nothing was upgraded
+    pub install: Option<InstallPlan>,
 }
 
 pub struct Upgrade;
 
 impl Upgrade {
-    pub async fn run(mods: &[String]) -> Result<UpgradeResult, String> {
-        let mut manager = ModManager::load()
-            .await
-            .map_err(|e| format!("Failed to initialize ModManager: {}", e))?;
+    pub async fn run(mods: &[String]) -> Result<UpgradeResult, McpmError> {
+        Self::run_with(ModManager::load().await?, mods).await
+    }
+
+    /// [`Self::run`] on an already loaded manager
+    pub async fn run_with(
+        manager: ModManager,
+        mods: &[String],
+    ) -> Result<UpgradeResult, McpmError> {
+        let (mut manager, _, result) = Self::resolve(manager, mods).await?;
+
+        manager.save_all()?;
+
+        Ok(result)
+    }
+
+    /// Compute the upgrade and the install that would follow it, without writing anything.
+    /// `with` selects optional mods to install, see [`Install::plan`].
+    pub async fn plan(
+        mods: &[String],
+        no_cache: bool,
+        force_rehash: bool,
+        with: &[String],
+    ) -> Result<(UpgradeResult, InstallPlan), McpmError> {
+        let (manager, before, result) = Self::resolve(ModManager::load().await?, mods).await?;
+        let plan = Install::plan_with(manager, &before, no_cache, force_rehash, with).await?;
+
+        Ok((result, plan))
+    }
+
+    async fn resolve(
+        mut manager: ModManager,
+        mods: &[String],
+    ) -> Result<(ModManager, LockFile, UpgradeResult), McpmError> {
+        let before_lock = manager.lock_service.lock.clone();
 
         let all_mods = manager.manifest.mods_as_entries();
         let to_upgrade: Vec<_> = if mods.is_empty() {
@@ -25,7 +77,9 @@ impl Upgrade {
         };
 
         if to_upgrade.is_empty() {
-            return Err("No matching mods found to upgrade".into());
+            return Err(McpmError::NotFound(
+                "Mods to upgrade matching the query".into(),
+            ));
         }
 
         let before_versions: HashMap<_, _> = to_upgrade
@@ -33,35 +87,60 @@ impl Upgrade {
             .filter_map(|m| manager.lock_service.get_version(m).map(|v| (m.to_key(), v)))
             .collect();
 
+        manager.validate_minecraft(&to_upgrade, true).await?;
         for entry in &to_upgrade {
-            manager
-                .refresh_mod(entry, None, true)
-                .await
-                .map_err(|e| format!("Failed to refresh {}: {}", entry.slug, e))?;
+            manager.refresh_mod(entry, None, true).await?;
         }
 
-        manager
-            .save_all()
-            .map_err(|e| format!("Failed to save lockfile: {}", e))?;
-
         let mut upgraded = vec![];
+        // The loader only moves along when the whole pack is upgraded
+        if mods.is_empty() {
+            let loader_before = manager.lock_service.lock.loader.clone();
+            manager.refresh_loader(true).await?;
+            let loader_after = &manager.lock_service.lock.loader;
+            if loader_before.is_some() && &loader_before != loader_after {
+                upgraded.push(LockChange {
+                    key: format!("loader:{}", as_str(&manager.manifest.modloader)),
+                    before: loader_before.map(|l| l.version),
+                    after: loader_after.as_ref().map(|l| l.version.clone()),
+                });
+            }
+        }
+
         let mut unchanged = 0;
+        let mut pinned = vec![];
+        let mut held = vec![];
 
         for entry in &to_upgrade {
             let key = entry.to_key();
+            if entry.options.version_id.is_some() {
+                held.push(key);
+                continue;
+            }
+            if entry.options.pinned.is_some() {
+                pinned.push(key);
+                continue;
+            }
+
             let before = before_versions.get(&key).cloned();
             let after = manager.lock_service.get_version(entry);
 
             if before != after {
-                upgraded.push((key, before, after));
+                upgraded.push(LockChange { key, before, after });
             } else {
                 unchanged += 1;
             }
         }
 
-        Ok(UpgradeResult {
-            upgraded,
-            unchanged,
-        })
+        Ok((
+            manager,
+            before_lock,
+            UpgradeResult {
+                upgraded,
+                unchanged,
+                pinned,
+                held,
+            },
+        ))
     }
 }
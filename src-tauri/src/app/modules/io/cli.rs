@@ -1,25 +1,69 @@
 use async_trait::async_trait;
 use colored::*;
-use dialoguer::{Confirm, Input as DInput};
+use dialoguer::{Confirm, Input as DInput, Select};
+use indicatif::{MultiProgress, ProgressBar, ProgressDrawTarget, ProgressStyle};
 use std::{
+    collections::HashMap,
     error::Error,
-    io::{stdin, stdout, IsTerminal},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
 };
 
-use crate::app::modules::io::traits::PromptResult;
+use crate::app::{
+    is_interactive,
+    modules::io::traits::{select_without_prompt, PromptResult},
+    McpmError,
+};
 
-use super::traits::{IOConfig, Input, Output};
+use super::traits::{IOConfig, Input, Output, ProgressId, ProgressUnit};
 
 pub struct CliIO {
     cfg: IOConfig,
+    bars: MultiProgress,
+    tasks: Mutex<HashMap<ProgressId, ProgressBar>>,
+    next_id: AtomicU64,
 }
 impl CliIO {
     pub fn new(cfg: IOConfig) -> Self {
-        Self { cfg }
+        let bars = if cfg.quiet {
+            MultiProgress::with_draw_target(ProgressDrawTarget::hidden())
+        } else {
+            MultiProgress::new()
+        };
+
+        Self {
+            cfg,
+            bars,
+            tasks: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(0),
+        }
     }
 
-    fn interactive(&self) -> bool {
-        return !self.cfg.quiet && stdin().is_terminal() && stdout().is_terminal();
+    /// Print above the progress bars instead of through them
+    fn print(&self, line: String) {
+        self.bars.suspend(|| println!("{}", line));
+    }
+
+    fn eprint(&self, line: String) {
+        self.bars.suspend(|| eprintln!("{}", line));
+    }
+
+    fn style(unit: ProgressUnit, known_total: bool) -> ProgressStyle {
+        let template = match (unit, known_total) {
+            (ProgressUnit::Bytes, true) => {
+                "{spinner:.green} {msg:30!} [{bar:30.cyan/blue}] {bytes}/{total_bytes} ({eta})"
+            }
+            (ProgressUnit::Bytes, false) => "{spinner:.green} {msg:30!} {bytes}",
+            (ProgressUnit::Items, true) => {
+                "{spinner:.green} {msg:30!} [{bar:30.green}] {pos}/{len}"
+            }
+            (ProgressUnit::Items, false) => "{spinner:.green} {msg:30!} {pos}",
+        };
+        ProgressStyle::with_template(template)
+            .expect("valid progress template")
+            .progress_chars("=> ")
     }
 }
 
@@ -27,27 +71,59 @@ impl CliIO {
 impl Output for CliIO {
     fn debug(&self, msg: &str) {
         if self.cfg.verbose {
-            println!("{} {}", "[DEBUG]".purple().bold(), msg);
+            self.print(format!("{} {}", "[DEBUG]".purple().bold(), msg));
         }
     }
     fn info(&self, msg: &str) {
         if !self.cfg.quiet {
-            println!("{} {}", "[INFO]".blue().bold(), msg);
+            self.print(format!("{} {}", "[INFO]".blue().bold(), msg));
         }
     }
     fn success(&self, msg: &str) {
         if !self.cfg.quiet {
-            println!("{} {}", "[OK]".green().bold(), msg);
+            self.print(format!("{} {}", "[OK]".green().bold(), msg));
         }
     }
     fn warn(&self, msg: &str) {
-        eprintln!("{} {}", "[WARNING]".yellow().bold(), msg);
+        self.eprint(format!("{} {}", "[WARNING]".yellow().bold(), msg));
     }
     fn error(&self, msg: &str, err: Option<&dyn Error>) {
         let prefix = "[ERROR]".red().bold();
         match err {
-            Some(e) => eprintln!("{} {}: {}", prefix, msg, e),
-            None => eprintln!("{} {}", prefix, msg),
+            Some(e) => {
+                self.eprint(format!("{} {}: {}", prefix, msg, e));
+                let mut source = e.source();
+                while let Some(s) = source {
+                    self.eprint(format!("  caused by: {}", s));
+                    source = s.source();
+                }
+            }
+            None => self.eprint(format!("{} {}", prefix, msg)),
+        }
+    }
+
+    fn progress_start(&self, label: &str, total: Option<u64>, unit: ProgressUnit) -> ProgressId {
+        let id = ProgressId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let bar = match total {
+            Some(t) => ProgressBar::new(t),
+            None => ProgressBar::no_length(),
+        };
+        let bar = self.bars.add(bar);
+        bar.set_style(Self::style(unit, total.is_some()));
+        bar.set_message(label.to_string());
+
+        self.tasks.lock().unwrap().insert(id, bar);
+        id
+    }
+    fn progress_advance(&self, id: ProgressId, delta: u64) {
+        if let Some(bar) = self.tasks.lock().unwrap().get(&id) {
+            bar.inc(delta);
+        }
+    }
+    fn progress_finish(&self, id: ProgressId) {
+        if let Some(bar) = self.tasks.lock().unwrap().remove(&id) {
+            bar.finish_and_clear();
+            self.bars.remove(&bar);
         }
     }
 }
@@ -55,7 +131,7 @@ impl Output for CliIO {
 #[async_trait]
 impl Input for CliIO {
     async fn prompt(&self, message: &str, default: Option<String>) -> PromptResult<String> {
-        if !self.interactive() {
+        if !is_interactive() {
             return match default {
                 Some(v) => PromptResult::Response(v),
                 None => PromptResult::Cancel,
@@ -74,7 +150,10 @@ impl Input for CliIO {
         }
     }
     async fn confirm(&self, message: &str, default: bool) -> PromptResult<bool> {
-        if !self.interactive() {
+        if self.cfg.assume_yes {
+            return PromptResult::Response(true);
+        }
+        if !is_interactive() {
             return PromptResult::Response(default);
         }
 
@@ -84,6 +163,34 @@ impl Input for CliIO {
         }
     }
 
+    fn can_prompt(&self) -> bool {
+        is_interactive()
+    }
+
+    async fn select(
+        &self,
+        message: &str,
+        items: &[String],
+        default: usize,
+    ) -> Result<PromptResult<usize>, McpmError> {
+        if !is_interactive() {
+            return select_without_prompt(message, items);
+        }
+
+        let selection = self.bars.suspend(|| {
+            Select::new()
+                .with_prompt(message)
+                .items(items)
+                .default(default)
+                .interact_opt()
+        });
+
+        Ok(match selection {
+            Ok(Some(i)) => PromptResult::Response(i),
+            _ => PromptResult::Cancel,
+        })
+    }
+
     // TODO: Implement warn and error prompts when needed
     // async fn modal_warn(&self, m: &str) {
     //     eprintln!("{} {}", "[WARN]".yellow().bold(), m);
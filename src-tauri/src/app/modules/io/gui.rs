@@ -1,16 +1,119 @@
-use std::error::Error;
+use futures::channel::{mpsc, oneshot};
+use serde::Serialize;
+use std::{
+    error::Error,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
-use crate::app::modules::io::traits::PromptResult;
+use crate::app::{modules::io::traits::PromptResult, McpmError};
 
-use super::traits::{IOConfig, Input, Output};
+use super::traits::{IOConfig, Input, Output, ProgressId, ProgressUnit};
 use async_trait::async_trait;
 
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Success,
+    Warn,
+    Error,
+}
+
+/// Events sent to the frontend
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum GuiEvent {
+    Log {
+        level: LogLevel,
+        message: String,
+        error: Option<String>,
+    },
+    ProgressStart {
+        id: ProgressId,
+        label: String,
+        total: Option<u64>,
+        unit: ProgressUnit,
+    },
+    ProgressAdvance {
+        id: ProgressId,
+        delta: u64,
+    },
+    ProgressFinish {
+        id: ProgressId,
+    },
+}
+
+/// Questions the frontend has to answer in a modal.
+/// Answering `None` or dropping `respond` cancels the question.
+#[derive(Debug)]
+pub enum GuiRequest {
+    Prompt {
+        question: String,
+        default: Option<String>,
+        respond: oneshot::Sender<Option<String>>,
+    },
+    Confirm {
+        question: String,
+        default: bool,
+        respond: oneshot::Sender<Option<bool>>,
+    },
+    Select {
+        question: String,
+        items: Vec<String>,
+        default: usize,
+        respond: oneshot::Sender<Option<usize>>,
+    },
+}
+
 pub struct GuiIO {
     cfg: IOConfig,
+    next_id: AtomicU64,
+    requests: mpsc::UnboundedSender<GuiRequest>,
 }
 impl GuiIO {
-    pub fn new(cfg: IOConfig) -> Self {
-        Self { cfg }
+    /// Returns the IO and the receiving end of the questions for the frontend
+    pub fn new(cfg: IOConfig) -> (Self, mpsc::UnboundedReceiver<GuiRequest>) {
+        let (requests, receiver) = mpsc::unbounded();
+        let io = Self {
+            cfg,
+            next_id: AtomicU64::new(0),
+            requests,
+        };
+        (io, receiver)
+    }
+
+    #[cfg(feature = "gui")]
+    fn emit(&self, event: GuiEvent) {
+        crate::app::gui::emit_event(&event);
+    }
+
+    // Without the GUI there is no frontend to send events to
+    #[cfg(not(feature = "gui"))]
+    fn emit(&self, _event: GuiEvent) {}
+
+    /// Send `request` to the frontend and wait for its answer
+    async fn ask<T>(
+        &self,
+        request: GuiRequest,
+        answer: oneshot::Receiver<Option<T>>,
+    ) -> PromptResult<T> {
+        if self.requests.unbounded_send(request).is_err() {
+            return PromptResult::Cancel;
+        }
+
+        match answer.await {
+            Ok(Some(v)) => PromptResult::Response(v),
+            _ => PromptResult::Cancel,
+        }
+    }
+
+    fn log(&self, level: LogLevel, msg: &str, err: Option<&dyn Error>) {
+        self.emit(GuiEvent::Log {
+            level,
+            message: msg.to_string(),
+            error: err.map(|e| e.to_string()),
+        });
     }
 }
 
@@ -18,37 +121,93 @@ impl GuiIO {
 impl Output for GuiIO {
     fn debug(&self, msg: &str) {
         if self.cfg.verbose {
-            println!("[DEBUG] {msg}");
+            self.log(LogLevel::Debug, msg, None);
         }
     }
     fn info(&self, msg: &str) {
-        println!("[INFO] {msg}");
+        self.log(LogLevel::Info, msg, None);
     }
     fn success(&self, msg: &str) {
-        println!("[OK] {msg}");
+        self.log(LogLevel::Success, msg, None);
     }
     fn warn(&self, msg: &str) {
-        eprintln!("[WARN] {msg}");
+        self.log(LogLevel::Warn, msg, None);
     }
     fn error(&self, msg: &str, err: Option<&dyn Error>) {
-        match err {
-            Some(e) => eprintln!("[ERR] {msg}: {e}"),
-            None => eprintln!("[ERR] {msg}"),
-        }
+        self.log(LogLevel::Error, msg, err);
+    }
+
+    fn progress_start(&self, label: &str, total: Option<u64>, unit: ProgressUnit) -> ProgressId {
+        let id = ProgressId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.emit(GuiEvent::ProgressStart {
+            id,
+            label: label.to_string(),
+            total,
+            unit,
+        });
+        id
+    }
+    fn progress_advance(&self, id: ProgressId, delta: u64) {
+        self.emit(GuiEvent::ProgressAdvance { id, delta });
+    }
+    fn progress_finish(&self, id: ProgressId) {
+        self.emit(GuiEvent::ProgressFinish { id });
     }
 }
 
 #[async_trait]
 impl Input for GuiIO {
     async fn prompt(&self, message: &str, default: Option<String>) -> PromptResult<String> {
-        // TODO: Implement GUI modal prompt
-        return match default {
-            Some(v) => PromptResult::Response(v),
-            None => PromptResult::Cancel,
-        };
+        let (respond, answer) = oneshot::channel();
+        self.ask(
+            GuiRequest::Prompt {
+                question: message.to_string(),
+                default,
+                respond,
+            },
+            answer,
+        )
+        .await
     }
     async fn confirm(&self, message: &str, default: bool) -> PromptResult<bool> {
-        return PromptResult::Response(default);
+        if self.cfg.assume_yes {
+            return PromptResult::Response(true);
+        }
+
+        let (respond, answer) = oneshot::channel();
+        self.ask(
+            GuiRequest::Confirm {
+                question: message.to_string(),
+                default,
+                respond,
+            },
+            answer,
+        )
+        .await
+    }
+
+    fn can_prompt(&self) -> bool {
+        true
+    }
+
+    async fn select(
+        &self,
+        message: &str,
+        items: &[String],
+        default: usize,
+    ) -> Result<PromptResult<usize>, McpmError> {
+        let (respond, answer) = oneshot::channel();
+        let request = GuiRequest::Select {
+            question: message.to_string(),
+            items: items.to_vec(),
+            default,
+            respond,
+        };
+
+        Ok(match self.ask(request, answer).await {
+            PromptResult::Response(i) if i < items.len() => PromptResult::Response(i),
+            _ => PromptResult::Cancel,
+        })
     }
 
     // TODO: Implement warn and error prompts when needed
@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::app::modules::io::{
+        gui::{GuiIO, GuiRequest},
+        traits::{IOConfig, Input, LogFormat, PromptResult},
+    };
+
+    // Prompts and confirms are answered by the frontend's modals,
+    // a cancelled modal answers `None`.
+
+    fn cfg(assume_yes: bool) -> IOConfig {
+        IOConfig {
+            verbose: false,
+            quiet: true,
+            format: LogFormat::Text,
+            assume_yes,
+        }
+    }
+
+    #[tokio::test]
+    async fn prompt_returns_the_frontend_answer() {
+        let (io, mut requests) = GuiIO::new(cfg(false));
+
+        let frontend = tokio::spawn(async move {
+            let Some(GuiRequest::Prompt {
+                question,
+                default,
+                respond,
+            }) = requests.next().await
+            else {
+                panic!("expected a prompt");
+            };
+            respond.send(Some("my-pack".into())).unwrap();
+            (question, default)
+        });
+
+        let answer = io.prompt("Pack name", Some("pack".into())).await;
+        assert!(matches!(answer, PromptResult::Response(ref name) if name == "my-pack"));
+
+        let (question, default) = frontend.await.unwrap();
+        assert_eq!(question, "Pack name");
+        assert_eq!(default.as_deref(), Some("pack"));
+    }
+
+    #[tokio::test]
+    async fn cancelled_modals_cancel_the_question() {
+        let (io, mut requests) = GuiIO::new(cfg(false));
+
+        let frontend = tokio::spawn(async move {
+            while let Some(request) = requests.next().await {
+                match request {
+                    GuiRequest::Confirm { respond, .. } => respond.send(None).unwrap(),
+                    // Closing the modal without answering
+                    GuiRequest::Prompt { respond, .. } => drop(respond),
+                    GuiRequest::Select { .. } => panic!("unexpected select"),
+                }
+            }
+        });
+
+        assert!(matches!(
+            io.confirm("Remove sodium?", true).await,
+            PromptResult::Cancel
+        ));
+        assert!(matches!(
+            io.prompt("Pack name", None).await,
+            PromptResult::Cancel
+        ));
+
+        drop(io);
+        frontend.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn confirm_is_not_asked_with_assume_yes() {
+        let (io, requests) = GuiIO::new(cfg(true));
+        drop(requests);
+
+        assert!(matches!(
+            io.confirm("Remove sodium?", false).await,
+            PromptResult::Response(true)
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use async_trait::async_trait;
+use chrono::{SecondsFormat, Utc};
+use serde_json::{json, Map, Value};
+use std::{
+    error::Error,
+    io::{stderr, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+use crate::app::{
+    modules::io::traits::{select_without_prompt, PromptResult},
+    McpmError,
+};
+
+use super::traits::{IOConfig, Input, Output, ProgressId, ProgressUnit};
+
+/// Writes every message as one JSON object per line, for tools that parse mcpm logs.
+/// Prompts are never shown, they are answered with their default and logged.
+/// Progress is logged when a task starts and finishes, not on every step.
+pub struct JsonIO {
+    cfg: IOConfig,
+    out: Mutex<Box<dyn Write + Send>>,
+    next_id: AtomicU64,
+}
+
+impl JsonIO {
+    pub fn new(cfg: IOConfig) -> Self {
+        Self::with_writer(cfg, Box::new(stderr()))
+    }
+
+    pub fn with_writer(cfg: IOConfig, out: Box<dyn Write + Send>) -> Self {
+        Self {
+            cfg,
+            out: Mutex::new(out),
+            next_id: AtomicU64::new(0),
+        }
+    }
+
+    fn emit(&self, level: &str, msg: &str, fields: Map<String, Value>) {
+        let mut event = json!({
+            "ts": Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            "level": level,
+            "message": msg,
+        });
+        event
+            .as_object_mut()
+            .expect("event is an object")
+            .extend(fields);
+
+        // A poisoned lock or closed stderr must not take the command down with it
+        if let Ok(mut out) = self.out.lock() {
+            let _ = writeln!(out, "{}", event);
+            let _ = out.flush();
+        }
+    }
+}
+
+#[async_trait]
+impl Output for JsonIO {
+    fn debug(&self, msg: &str) {
+        if self.cfg.verbose {
+            self.emit("debug", msg, Map::new());
+        }
+    }
+    fn info(&self, msg: &str) {
+        if !self.cfg.quiet {
+            self.emit("info", msg, Map::new());
+        }
+    }
+    fn success(&self, msg: &str) {
+        if !self.cfg.quiet {
+            self.emit("success", msg, Map::new());
+        }
+    }
+    fn warn(&self, msg: &str) {
+        self.emit("warn", msg, Map::new());
+    }
+    fn error(&self, msg: &str, err: Option<&dyn Error>) {
+        let mut fields = Map::new();
+        if let Some(e) = err {
+            let mut causes = vec![];
+            let mut source = e.source();
+            while let Some(s) = source {
+                causes.push(Value::from(s.to_string()));
+                source = s.source();
+            }
+            fields.insert("error".into(), e.to_string().into());
+            fields.insert("causes".into(), causes.into());
+        }
+        self.emit("error", msg, fields);
+    }
+
+    fn progress_start(&self, label: &str, total: Option<u64>, unit: ProgressUnit) -> ProgressId {
+        let id = ProgressId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        if !self.cfg.quiet {
+            let mut fields = Map::new();
+            fields.insert("progress".into(), id.0.into());
+            fields.insert("total".into(), total.into());
+            fields.insert("unit".into(), json!(unit));
+            self.emit("progress_start", label, fields);
+        }
+        id
+    }
+    fn progress_advance(&self, _id: ProgressId, _delta: u64) {}
+    fn progress_finish(&self, id: ProgressId) {
+        if !self.cfg.quiet {
+            let mut fields = Map::new();
+            fields.insert("progress".into(), id.0.into());
+            self.emit("progress_finish", "", fields);
+        }
+    }
+}
+
+#[async_trait]
+impl Input for JsonIO {
+    async fn prompt(&self, message: &str, default: Option<String>) -> PromptResult<String> {
+        let mut fields = Map::new();
+        fields.insert("kind".into(), "prompt".into());
+        fields.insert("answer".into(), default.clone().into());
+        self.emit("prompt", message, fields);
+
+        match default {
+            Some(v) => PromptResult::Response(v),
+            None => PromptResult::Cancel,
+        }
+    }
+    async fn confirm(&self, message: &str, default: bool) -> PromptResult<bool> {
+        let mut fields = Map::new();
+        let answer = default || self.cfg.assume_yes;
+        fields.insert("kind".into(), "confirm".into());
+        fields.insert("answer".into(), answer.into());
+        self.emit("prompt", message, fields);
+
+        PromptResult::Response(answer)
+    }
+
+    fn can_prompt(&self) -> bool {
+        false
+    }
+
+    async fn select(
+        &self,
+        message: &str,
+        items: &[String],
+        _default: usize,
+    ) -> Result<PromptResult<usize>, McpmError> {
+        let result = select_without_prompt(message, items);
+
+        let answer = match &result {
+            Ok(PromptResult::Response(i)) => Value::from(*i),
+            _ => Value::Null,
+        };
+        let mut fields = Map::new();
+        fields.insert("kind".into(), "select".into());
+        fields.insert("items".into(), items.into());
+        fields.insert("answer".into(), answer);
+        self.emit("prompt", message, fields);
+
+        result
+    }
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+    use std::{
+        io::{self, Write},
+        sync::{Arc, Mutex},
+    };
+
+    use crate::app::{
+        modules::io::{
+            json::JsonIO,
+            traits::{IOConfig, Input, LogFormat, Output, PromptResult},
+        },
+        McpmError,
+    };
+
+    // Every call becomes one parseable line with a timestamp and level,
+    // and prompts are answered with their defaults.
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn events(&self) -> Vec<Value> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(|l| serde_json::from_str(l).expect("each line is a JSON event"))
+                .collect()
+        }
+    }
+
+    fn json_io(verbose: bool, quiet: bool) -> (JsonIO, Buffer) {
+        let buffer = Buffer::default();
+        let cfg = IOConfig {
+            verbose,
+            quiet,
+            format: LogFormat::Json,
+            assume_yes: false,
+        };
+        (JsonIO::with_writer(cfg, Box::new(buffer.clone())), buffer)
+    }
+
+    #[test]
+    fn writes_one_event_per_line_with_level_and_timestamp() {
+        let (io, buffer) = json_io(false, false);
+
+        io.debug("hidden without --verbose");
+        io.info("Downloading sodium");
+        io.warn("Mod 'x' is disabled");
+
+        let events = buffer.events();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["level"], "info");
+        assert_eq!(events[0]["message"], "Downloading sodium");
+        assert!(events[0]["ts"].as_str().unwrap().ends_with('Z'));
+        assert_eq!(events[1]["level"], "warn");
+    }
+
+    #[test]
+    fn errors_carry_their_source_chain() {
+        let (io, buffer) = json_io(false, true);
+        let err = McpmError::io(
+            "Failed to save lockfile",
+            io::Error::new(io::ErrorKind::PermissionDenied, "read-only"),
+        );
+
+        io.success("hidden with --quiet");
+        io.error("install failed", Some(&err));
+
+        let events = buffer.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["level"], "error");
+        assert_eq!(events[0]["error"], "Failed to save lockfile");
+        assert_eq!(events[0]["causes"][0], "read-only");
+    }
+
+    #[tokio::test]
+    async fn prompts_answer_with_defaults() {
+        let (io, buffer) = json_io(false, false);
+
+        let name = io.prompt("Pack name", Some("pack".into())).await;
+        let cancelled = io.prompt("Pack name", None).await;
+        let confirmed = io.confirm("Continue?", true).await;
+
+        assert!(matches!(name, PromptResult::Response(ref v) if v == "pack"));
+        assert!(cancelled.is_cancel());
+        assert!(matches!(confirmed, PromptResult::Response(true)));
+
+        let events = buffer.events();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[1]["answer"], Value::Null);
+        assert_eq!(events[2]["kind"], "confirm");
+    }
+}
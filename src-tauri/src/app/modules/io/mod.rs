@@ -1,10 +1,15 @@
 pub mod cli;
 pub mod gui;
+pub mod json;
 pub mod traits;
 
 use std::sync::Arc;
 
-use crate::app::modules::io::{cli::CliIO, traits::IOConfig, traits::IO};
+use crate::app::modules::io::{
+    cli::CliIO,
+    json::JsonIO,
+    traits::{IOConfig, LogFormat, IO},
+};
 use once_cell::sync::OnceCell;
 
 pub type IOInstance = Arc<dyn IO + Send + Sync>;
@@ -12,17 +17,30 @@ static IO: OnceCell<IOInstance> = OnceCell::new();
 
 #[cfg(not(feature = "gui"))]
 pub async fn init_io(cfg: IOConfig) {
-    let io: IOInstance = Arc::new(CliIO::new(cfg));
-    IO.set(io).ok();
+    IO.set(terminal_io(cfg)).ok();
 }
 
 #[cfg(feature = "gui")]
 pub async fn init_io(cfg: IOConfig) {
-    // TODO: Replace with GUI-specific instance
-    let io: IOInstance = Arc::new(CliIO::new(cfg));
-    IO.set(io).ok();
+    let (io, requests) = gui::GuiIO::new(cfg);
+    crate::app::gui::serve_requests(requests);
+    IO.set(Arc::new(io)).ok();
+}
+
+fn terminal_io(cfg: IOConfig) -> IOInstance {
+    match cfg.format {
+        LogFormat::Text => Arc::new(CliIO::new(cfg)),
+        LogFormat::Json => Arc::new(JsonIO::new(cfg)),
+    }
 }
 
 pub fn use_io() -> IOInstance {
     IO.get().expect("IOService not initialized").clone()
 }
+
+#[cfg(test)]
+mod gui_spec;
+#[cfg(test)]
+mod json_spec;
+#[cfg(test)]
+mod select_spec;
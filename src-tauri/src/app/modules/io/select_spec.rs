@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+
+    use crate::app::{
+        modules::io::{
+            gui::{GuiIO, GuiRequest},
+            json::JsonIO,
+            traits::{IOConfig, Input, LogFormat, PageLoader, PromptResult},
+        },
+        McpmError,
+    };
+
+    // Selections that can't be asked must not guess: a single candidate is
+    // taken, more than one is an error. The GUI answers over its channel.
+
+    fn cfg() -> IOConfig {
+        IOConfig {
+            verbose: false,
+            quiet: true,
+            format: LogFormat::Json,
+            assume_yes: false,
+        }
+    }
+
+    fn labels(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// Pages of two numbered items, `pages` of them
+    fn pages(pages: usize) -> PageLoader<'static> {
+        Box::new(move |page| {
+            Box::pin(async move {
+                Ok(if page < pages {
+                    labels(&[&format!("{}a", page), &format!("{}b", page)])
+                } else {
+                    vec![]
+                })
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn select_without_prompts_takes_the_only_candidate() {
+        let io = JsonIO::with_writer(cfg(), Box::new(std::io::sink()));
+
+        let picked = io.select("Select a project", &labels(&["sodium"]), 0).await;
+        assert!(matches!(picked, Ok(PromptResult::Response(0))));
+    }
+
+    #[tokio::test]
+    async fn select_without_prompts_refuses_to_guess() {
+        let io = JsonIO::with_writer(cfg(), Box::new(std::io::sink()));
+
+        let err = io
+            .select("Select a project", &labels(&["sodium", "sodium-extra"]), 0)
+            .await
+            .unwrap_err();
+        match err {
+            McpmError::Ambiguous { candidates, .. } => {
+                assert_eq!(candidates, labels(&["sodium", "sodium-extra"]))
+            }
+            e => panic!("expected an ambiguity error, got {e:?}"),
+        }
+
+        let paginated = io.select_paginated("Select a project", pages(2)).await;
+        assert!(matches!(paginated, Err(McpmError::Ambiguous { .. })));
+
+        let empty = io.select_paginated("Select a project", pages(0)).await;
+        assert!(matches!(empty, Ok(PromptResult::Cancel)));
+    }
+
+    #[tokio::test]
+    async fn gui_answers_selections_over_its_channel() {
+        let (io, mut requests) = GuiIO::new(cfg());
+
+        let frontend = tokio::spawn(async move {
+            let mut seen = vec![];
+            while let Some(GuiRequest::Select { items, respond, .. }) = requests.next().await {
+                seen.push(items.clone());
+                // Ask for the next page once, then take the third item
+                let answer = if seen.len() == 1 { items.len() - 1 } else { 2 };
+                respond.send(Some(answer)).unwrap();
+            }
+            seen
+        });
+
+        let picked = io.select_paginated("Select a project", pages(3)).await;
+        assert!(matches!(picked, Ok(PromptResult::Response(2))));
+
+        drop(io);
+        let seen = frontend.await.unwrap();
+        assert_eq!(seen[0], labels(&["0a", "0b", "Load more…"]));
+        assert_eq!(seen[1], labels(&["0a", "0b", "1a", "1b", "Load more…"]));
+    }
+
+    #[tokio::test]
+    async fn gui_selection_is_cancelled_when_the_frontend_drops_it() {
+        let (io, requests) = GuiIO::new(cfg());
+        drop(requests);
+
+        let picked = io
+            .select("Select a version", &labels(&["1.0", "2.0"]), 0)
+            .await;
+        assert!(matches!(picked, Ok(PromptResult::Cancel)));
+    }
+}
@@ -1,11 +1,28 @@
 use std::error::Error;
 
 use async_trait::async_trait;
+use clap::ValueEnum;
+use futures::future::BoxFuture;
+use serde::Serialize;
+
+use crate::app::McpmError;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum LogFormat {
+    /// Coloured, human readable lines
+    #[default]
+    Text,
+    /// One JSON event per line on stderr
+    Json,
+}
 
 #[derive(Debug, Clone, Copy)]
 pub struct IOConfig {
     pub verbose: bool,
     pub quiet: bool,
+    pub format: LogFormat,
+    /// Answer every confirmation with yes (`--yes`)
+    pub assume_yes: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -17,6 +34,17 @@ pub enum PromptResult<T> {
     Cancel,
 }
 
+/// Handle to a task started with [`Output::progress_start`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct ProgressId(pub u64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressUnit {
+    Bytes,
+    Items,
+}
+
 #[async_trait]
 pub trait Output {
     fn debug(&self, msg: &str);
@@ -24,13 +52,75 @@ pub trait Output {
     fn success(&self, msg: &str);
     fn warn(&self, msg: &str);
     fn error(&self, msg: &str, err: Option<&dyn Error>);
+
+    /// Start a task of `total` units, `None` when the size is unknown.
+    /// Several tasks can run at the same time.
+    fn progress_start(&self, label: &str, total: Option<u64>, unit: ProgressUnit) -> ProgressId;
+    fn progress_advance(&self, id: ProgressId, delta: u64);
+    fn progress_finish(&self, id: ProgressId);
 }
 
+/// Loads page `n` of a paginated selection, returning the labels of its items
+pub type PageLoader<'a> =
+    Box<dyn FnMut(usize) -> BoxFuture<'a, Result<Vec<String>, McpmError>> + Send + 'a>;
+
 #[async_trait]
 pub trait Input {
     async fn prompt(&self, question: &str, default: Option<String>) -> PromptResult<String>;
     async fn confirm(&self, question: &str, default: bool) -> PromptResult<bool>;
 
+    /// Whether this IO can ask the user at all
+    fn can_prompt(&self) -> bool;
+
+    /// Let the user pick one of `items`, returning its index.
+    /// Without prompts a single item is picked and more than one is ambiguous.
+    async fn select(
+        &self,
+        question: &str,
+        items: &[String],
+        default: usize,
+    ) -> Result<PromptResult<usize>, McpmError>;
+
+    /// Like [`Input::select`], loading one more page each time the user asks for it.
+    /// Without prompts only the first page is considered.
+    /// Returns the index across all loaded pages, or Cancel when nothing was found.
+    async fn select_paginated(
+        &self,
+        question: &str,
+        mut load_page: PageLoader<'_>,
+    ) -> Result<PromptResult<usize>, McpmError> {
+        let mut items = load_page(0).await?;
+        if items.is_empty() {
+            return Ok(PromptResult::Cancel);
+        }
+        if !self.can_prompt() {
+            return select_without_prompt(question, &items);
+        }
+
+        let mut page = 1;
+        let mut last_selected = 0;
+        loop {
+            let mut menu = items.clone();
+            menu.push(LOAD_MORE.into());
+
+            let selection = match self.select(question, &menu, last_selected).await? {
+                PromptResult::Response(i) => i,
+                PromptResult::Cancel => return Ok(PromptResult::Cancel),
+            };
+            if selection < items.len() {
+                return Ok(PromptResult::Response(selection));
+            }
+
+            let more = load_page(page).await?;
+            if more.is_empty() {
+                return Ok(PromptResult::Cancel);
+            }
+            last_selected = items.len();
+            items.extend(more);
+            page += 1;
+        }
+    }
+
     // TODO: Implement warn and error prompts when needed
     // async fn modal_warn(&self, message: &str);
     // async fn modal_error(&self, message: &str, err: Option<&dyn Error>);
@@ -41,6 +131,26 @@ pub trait Input {
 pub trait IO: Output + Input {}
 impl<T: Output + Input + Send + Sync> IO for T {}
 
+const LOAD_MORE: &str = "Load more…";
+
+/// Candidates listed in the error when a choice can't be made without asking
+const AMBIGUOUS_CANDIDATES: usize = 5;
+
+/// Answer a selection without asking: the only item, or an error naming the candidates
+pub fn select_without_prompt(
+    question: &str,
+    items: &[String],
+) -> Result<PromptResult<usize>, McpmError> {
+    match items.len() {
+        0 => Ok(PromptResult::Cancel),
+        1 => Ok(PromptResult::Response(0)),
+        _ => Err(McpmError::Ambiguous {
+            prompt: question.to_string(),
+            candidates: items.iter().take(AMBIGUOUS_CANDIDATES).cloned().collect(),
+        }),
+    }
+}
+
 impl<T> PromptResult<T> {
     pub fn unwrap(self) -> T {
         match self {
@@ -0,0 +1,7 @@
+pub mod models;
+mod services;
+
+pub use services::LoaderService;
+
+#[cfg(test)]
+mod services_spec;
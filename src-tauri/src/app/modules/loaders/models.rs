@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+
+use crate::app::modules::manifest::models::ModLoader;
+
+/// A loader release for one Minecraft version
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoaderVersion {
+    pub version: String,
+    /// Not marked as a beta by the loader's metadata
+    pub stable: bool,
+}
+
+/// The loader version in `mcpm.lock`, so every install of a pack runs the same loader
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LockedLoader {
+    pub name: ModLoader,
+    pub version: String,
+    /// Minecraft version the loader version was resolved for
+    pub minecraft_version: String,
+}
+
+/// `GET /v2/versions/loader/<mc>` of Fabric meta, `/v3/...` of Quilt meta
+#[derive(Debug, Deserialize)]
+pub(super) struct MetaEntry {
+    pub loader: MetaLoader,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct MetaLoader {
+    pub version: String,
+    /// Only Fabric reports this
+    pub stable: Option<bool>,
+}
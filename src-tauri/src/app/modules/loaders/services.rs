@@ -0,0 +1,162 @@
+use reqwest::{Client, StatusCode};
+
+use super::models::{LoaderVersion, MetaEntry};
+use crate::app::{
+    helpers::{as_str, http},
+    modules::manifest::models::ModLoader,
+    Config, McpmError,
+};
+
+/// Default metadata base URLs, overridden by `mirrors.<loader>`
+const FABRIC_META: &str = "https://meta.fabricmc.net";
+const QUILT_META: &str = "https://meta.quiltmc.org";
+const NEOFORGE_MAVEN: &str = "https://maven.neoforged.net/releases";
+const FORGE_MAVEN: &str = "https://maven.minecraftforge.net";
+
+/// Loader versions from Fabric and Quilt meta and the NeoForge and Forge mavens
+pub struct LoaderService {
+    client: Client,
+}
+
+impl Default for LoaderService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoaderService {
+    pub fn new() -> Self {
+        Self {
+            client: http::client(),
+        }
+    }
+
+    /// Loader versions for `minecraft_version`, newest first
+    pub async fn versions(
+        &self,
+        loader: ModLoader,
+        minecraft_version: &str,
+    ) -> Result<Vec<LoaderVersion>, McpmError> {
+        let base = base_url(loader);
+        match loader {
+            ModLoader::Fabric | ModLoader::Quilt => {
+                let api = if loader == ModLoader::Fabric {
+                    "v2"
+                } else {
+                    "v3"
+                };
+                let url = format!("{}/{}/versions/loader/{}", base, api, minecraft_version);
+                let entries: Vec<MetaEntry> =
+                    serde_json::from_str(&self.get(&url).await?.unwrap_or("[]".into()))
+                        .map_err(|e| McpmError::provider_response(&url, e))?;
+                Ok(entries
+                    .into_iter()
+                    .map(|e| LoaderVersion {
+                        stable: e.loader.stable.unwrap_or(!e.loader.version.contains('-')),
+                        version: e.loader.version,
+                    })
+                    .collect())
+            }
+            ModLoader::NeoForge => {
+                let url = format!("{}/net/neoforged/neoforge/maven-metadata.xml", base);
+                let metadata = self.get(&url).await?.unwrap_or_default();
+                Ok(neoforge_versions(
+                    &maven_versions(&metadata),
+                    minecraft_version,
+                ))
+            }
+            ModLoader::Forge => {
+                let url = format!("{}/net/minecraftforge/forge/maven-metadata.xml", base);
+                let metadata = self.get(&url).await?.unwrap_or_default();
+                Ok(forge_versions(
+                    &maven_versions(&metadata),
+                    minecraft_version,
+                ))
+            }
+        }
+    }
+
+    /// GET a body as text, `Ok(None)` when the URL doesn't exist
+    async fn get(&self, url: &str) -> Result<Option<String>, McpmError> {
+        http::ensure_online(url)?;
+
+        let res = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| McpmError::network(url, e))?;
+        // Fabric answers 400 for Minecraft versions it doesn't know
+        if matches!(
+            res.status(),
+            StatusCode::NOT_FOUND | StatusCode::BAD_REQUEST
+        ) {
+            return Ok(None);
+        }
+        res.error_for_status()
+            .map_err(|e| McpmError::network(url, e))?
+            .text()
+            .await
+            .map(Some)
+            .map_err(|e| McpmError::network(url, e))
+    }
+}
+
+fn base_url(loader: ModLoader) -> String {
+    let default = match loader {
+        ModLoader::Fabric => FABRIC_META,
+        ModLoader::Quilt => QUILT_META,
+        ModLoader::NeoForge => NEOFORGE_MAVEN,
+        ModLoader::Forge => FORGE_MAVEN,
+    };
+    Config::get()
+        .mirrors
+        .get(&as_str(&loader))
+        .map(|m| m.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| default.to_string())
+}
+
+/// The `<version>` entries of a `maven-metadata.xml`, oldest first
+pub(super) fn maven_versions(metadata: &str) -> Vec<String> {
+    metadata
+        .split("<version>")
+        .skip(1)
+        .filter_map(|s| s.split_once("</version>"))
+        .map(|(v, _)| v.trim().to_string())
+        .collect()
+}
+
+/// NeoForge numbers its versions after Minecraft without the leading `1.`:
+/// `21.1.77` is for 1.21.1 and `21.0.167` for 1.21
+pub(super) fn neoforge_versions(all: &[String], minecraft_version: &str) -> Vec<LoaderVersion> {
+    let short = minecraft_version
+        .strip_prefix("1.")
+        .unwrap_or(minecraft_version);
+    let prefix = match short.split('.').count() {
+        1 => format!("{}.0.", short),
+        _ => format!("{}.", short),
+    };
+    all.iter()
+        .rev()
+        .filter(|v| v.starts_with(&prefix))
+        .map(|v| LoaderVersion {
+            version: v.clone(),
+            stable: !v.contains("beta"),
+        })
+        .collect()
+}
+
+/// Forge versions are published as `<minecraft>-<forge>`, e.g. `1.21.1-52.0.16`
+pub(super) fn forge_versions(all: &[String], minecraft_version: &str) -> Vec<LoaderVersion> {
+    let prefix = format!("{}-", minecraft_version);
+    all.iter()
+        .rev()
+        .filter_map(|v| v.strip_prefix(&prefix))
+        // Older Forge versions repeat the Minecraft version at the end
+        .map(|v| v.split('-').next().unwrap_or(v))
+        .map(|v| LoaderVersion {
+            version: v.to_string(),
+            stable: true,
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+    use crate::app::modules::loaders::{
+        models::LoaderVersion,
+        services::{forge_versions, maven_versions, neoforge_versions},
+    };
+
+    const METADATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <versioning>
+    <versions>
+      <version>20.4.237</version>
+      <version>21.0.167</version>
+      <version>21.1.1-beta</version>
+      <version>21.1.77</version>
+      <version>1.20.1-47.3.0</version>
+      <version>1.21.1-52.0.16</version>
+      <version>1.7.10-10.13.4.1614-1.7.10</version>
+    </versions>
+  </versioning>
+</metadata>"#;
+
+    fn names(versions: Vec<LoaderVersion>) -> Vec<String> {
+        versions.into_iter().map(|v| v.version).collect()
+    }
+
+    #[test]
+    fn maven_versions_are_matched_to_minecraft() {
+        let all = maven_versions(METADATA);
+        assert_eq!(all.len(), 7);
+
+        let neoforge = neoforge_versions(&all, "1.21.1");
+        assert_eq!(names(neoforge.clone()), vec!["21.1.77", "21.1.1-beta"]);
+        assert!(neoforge[0].stable && !neoforge[1].stable);
+        assert_eq!(names(neoforge_versions(&all, "1.21")), vec!["21.0.167"]);
+
+        assert_eq!(names(forge_versions(&all, "1.21.1")), vec!["52.0.16"]);
+        assert_eq!(names(forge_versions(&all, "1.7.10")), vec!["10.13.4.1614"]);
+        assert!(forge_versions(&all, "1.21.8").is_empty());
+    }
+}
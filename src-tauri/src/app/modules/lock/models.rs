@@ -1,20 +1,78 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::app::{
+    helpers::schema::{migrate, Migration, MigrationReport},
+    modules::loaders::models::LockedLoader,
+};
+
+/// Schema version written by this build, see [`MIGRATIONS`]
+pub const LOCK_SCHEMA: u32 = 1;
+
+/// Upgrades from older lock schemas, in order
+pub const MIGRATIONS: [Migration; 1] = [Migration {
+    from: 0,
+    description: "record the schema version",
+    apply: |_| {},
+}];
+
 /// A single resolved dependency
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LockEntry {
     pub id: String,      // Identifier that never changes (as backup)
     pub version: String, // resolved version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>, // provider id of the resolved version
     pub minecraft_versions: Vec<String>,
     pub url: String,
     pub hash: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>, // download size in bytes, if the provider reports it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>, // project ids of required dependencies
+}
+
+impl LockEntry {
+    /// File name used for this entry in the cache and mods directory
+    pub fn file_name(&self, key: &str) -> String {
+        format!("{}-{}.jar", key, self.version)
+    }
 }
 
 /// The lock file structure
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct LockFile {
+    pub schema_version: u32,
     // pub manifest_version: String, // from manifest.version
     // pub minecraft_version: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loader: Option<LockedLoader>,
     pub mods: HashMap<String, LockEntry>,
 }
+
+impl LockFile {
+    /// Parse lock content, migrated to [`LOCK_SCHEMA`]
+    pub fn parse(
+        content: &str,
+    ) -> Result<(Self, MigrationReport), Box<dyn std::error::Error + Send + Sync>> {
+        let mut value: serde_json::Value = serde_json::from_str(content)?;
+        let report = migrate(&mut value, LOCK_SCHEMA, &MIGRATIONS)?;
+        let lock = if report.is_empty() {
+            // Straight from the text, so errors point at a line and column
+            serde_json::from_str(content)?
+        } else {
+            serde_json::from_value(value)?
+        };
+        Ok((lock, report))
+    }
+}
+
+impl Default for LockFile {
+    fn default() -> Self {
+        Self {
+            schema_version: LOCK_SCHEMA,
+            loader: None,
+            mods: HashMap::new(),
+        }
+    }
+}
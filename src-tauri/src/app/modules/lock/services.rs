@@ -6,11 +6,13 @@ use crate::app::{
     },
     modules::{
         io::use_io,
+        loaders::{models::LockedLoader, LoaderService},
         manifest::models::{Manifest, ModEntry, VersionSpec},
         repositories::{models::VersionResult, RepositoryService},
     },
+    Config, McpmError,
 };
-use std::collections::HashMap;
+use std::{io, path::PathBuf};
 
 const LOCK_FILE: &str = "mcpm.lock";
 
@@ -20,23 +22,87 @@ pub struct LockService {
 
 impl LockService {
     pub fn exists() -> bool {
-        std::path::Path::new(LOCK_FILE).exists()
+        Self::path().exists()
     }
 
-    pub fn load() -> Self {
-        let lock = std::fs::read_to_string(LOCK_FILE)
-            .ok()
-            .and_then(|s| serde_json::from_str(&s).ok())
-            .unwrap_or(LockFile {
-                mods: HashMap::new(),
-            });
+    /// `mcpm.lock` next to the manifest
+    pub fn path() -> PathBuf {
+        Config::get().project_dir.join(LOCK_FILE)
+    }
+
+    /// The lock on disk, migrated to the current schema, or an empty one if there is none yet
+    pub fn load() -> Result<Self, McpmError> {
+        let path = Self::path();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(c) => c,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Ok(Self {
+                    lock: LockFile::default(),
+                })
+            }
+            Err(e) => {
+                return Err(McpmError::io(
+                    format!("Failed to read {}", path.display()),
+                    e,
+                ))
+            }
+        };
+        let (lock, report) = LockFile::parse(&content).map_err(|source| McpmError::LockParse {
+            path: path.clone(),
+            source,
+        })?;
 
-        Self { lock }
+        if !report.is_empty() {
+            use_io().info(&format!(
+                "Upgraded {} from schema {} to {}: {}",
+                path.display(),
+                report.from,
+                report.to,
+                report.steps.join("; ")
+            ));
+        }
+        Ok(Self { lock })
     }
 
     pub fn save(&self) -> std::io::Result<()> {
         let content = serde_json::to_string_pretty(&self.lock).unwrap();
-        std::fs::write(LOCK_FILE, content)
+        std::fs::write(Self::path(), content)
+    }
+
+    /// `provider:id` to query for an entry. The manifest's id, then the locked one,
+    /// survive slug renames.
+    pub fn project_ref(&self, manifest_mod: &ModEntry) -> String {
+        match (&manifest_mod.id, self.lock.mods.get(&manifest_mod.to_key())) {
+            (None, Some(entry)) => format!("{}:{}", manifest_mod.provider, entry.id),
+            _ => manifest_mod.project_ref(),
+        }
+    }
+
+    /// Whether [`Self::update_entry`] would resolve the entry again: its lock
+    /// entry is missing or off the spec, or an upgrade was asked for
+    pub fn needs_update(
+        &self,
+        manifest_mod: &ModEntry,
+        manifest: &Manifest,
+        upgrade: bool,
+    ) -> bool {
+        // Pinned entries only move when the pin itself changes
+        if upgrade && !manifest_mod.is_frozen() {
+            return true;
+        }
+        match (
+            self.lock.mods.get(&manifest_mod.to_key()),
+            &manifest_mod.options.version_id,
+        ) {
+            (Some(entry), Some(id)) => entry.version_id.as_ref() != Some(id),
+            (Some(entry), None) => !satisfies(
+                &manifest_mod.effective_version(),
+                &entry.version,
+                &entry.minecraft_versions,
+                manifest.channel_for(manifest_mod),
+            ),
+            (None, _) => true,
+        }
     }
 
     pub async fn update_entry(
@@ -46,25 +112,16 @@ impl LockService {
         repo_service: &RepositoryService,
         available: Option<&[VersionResult]>,
         upgrade: bool,
-    ) -> bool {
-        let io = use_io();
-
+    ) -> Result<(), McpmError> {
         let key = manifest_mod.to_key();
 
-        let prev = &self.lock.mods.get(&key);
-
-        let version_outdated = match prev {
-            Some(entry) => !satisfies(&manifest_mod.version, &entry.version),
-            None => true,
-        };
-        let project_id = match prev {
-            Some(entry) => &entry.id,
-            None => &manifest_mod.slug,
-        };
+        let spec = manifest_mod.effective_version();
+        let version_id = &manifest_mod.options.version_id;
+        let project_ref = self.project_ref(manifest_mod);
 
-        if !upgrade && !version_outdated {
+        if !self.needs_update(manifest_mod, manifest, upgrade) {
             // Silently skipping, version satisfies spec and no upgrade requested
-            return true;
+            return Ok(());
         }
 
         let versions: Vec<VersionResult> = match available {
@@ -72,52 +129,122 @@ impl LockService {
             None => {
                 repo_service
                     .get_versions(
-                        &project_id,
-                        &[manifest.minecraft_version.clone()],
+                        &project_ref,
+                        std::slice::from_ref(&manifest.minecraft_version),
                         &[as_str(&manifest.modloader)],
                     )
-                    .await
+                    .await?
             }
         };
 
         if versions.is_empty() {
-            io.error(
-                &format!("No compatible versions found for '{}'", manifest_mod.slug),
-                None,
-            );
-            return false;
+            return Err(McpmError::NoCompatibleVersion {
+                slug: manifest_mod.slug.clone(),
+                detail: format!(
+                    "Minecraft {} + {:?}",
+                    manifest.minecraft_version, manifest.modloader
+                ),
+            });
         }
 
-        let resolved = match &manifest_mod.version {
-            VersionSpec::Exact(v) => versions.iter().find(|vr| &vr.version == v).cloned(),
-            VersionSpec::Range(r) => resolve_version(r.as_str(), &versions),
+        let resolved = match (version_id, &spec) {
+            (Some(id), _) => versions.iter().find(|vr| &vr.version_id == id).cloned(),
+            (None, VersionSpec::Exact(v)) => versions.iter().find(|vr| &vr.version == v).cloned(),
+            (None, VersionSpec::Range(r)) => {
+                resolve_version(r.as_str(), &versions, manifest.channel_for(manifest_mod))
+            }
         };
 
-        match resolved {
-            Some(resolved) => {
-                self.lock.mods.insert(
-                    key,
-                    LockEntry {
-                        id: resolved.mod_id,
-                        version: resolved.version,
-                        url: resolved.url,
-                        minecraft_versions: resolved.minecraft_versions,
-                        hash: resolved.hash,
-                    },
-                );
-            }
-            None => {
-                io.error(
-                    &format!(
-                        "Failed to resolve '{}' with version spec {:?}",
-                        manifest_mod.slug, manifest_mod.version
-                    ),
-                    None,
-                );
-            }
+        let resolved = resolved.ok_or_else(|| McpmError::NoCompatibleVersion {
+            slug: manifest_mod.slug.clone(),
+            detail: match version_id {
+                Some(id) => format!("no version with id '{}'", id),
+                None => format!(
+                    "nothing matches version spec '{}' on the {} channel",
+                    spec,
+                    manifest.channel_for(manifest_mod)
+                ),
+            },
+        })?;
+
+        self.lock.mods.insert(
+            key,
+            LockEntry {
+                id: resolved.mod_id,
+                version: resolved.version,
+                version_id: Some(resolved.version_id),
+                url: resolved.url,
+                minecraft_versions: resolved.minecraft_versions,
+                hash: resolved.hash,
+                size: resolved.size,
+                dependencies: resolved.dependencies,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Lock the manifest's loader version, or the newest stable one when it sets none.
+    /// The locked version is kept while it still fits the manifest, unless upgrading.
+    pub async fn update_loader(
+        &mut self,
+        manifest: &Manifest,
+        loaders: &LoaderService,
+        upgrade: bool,
+    ) -> Result<(), McpmError> {
+        let fits = self.lock.loader.as_ref().is_some_and(|l| {
+            l.name == manifest.modloader
+                && l.minecraft_version == manifest.minecraft_version
+                && manifest
+                    .loader_version
+                    .as_ref()
+                    .is_none_or(|v| v == &l.version)
+        });
+        let upgrade = upgrade && manifest.loader_version.is_none();
+        if fits && !upgrade {
+            return Ok(());
         }
 
-        return true;
+        let versions = match loaders
+            .versions(manifest.modloader, &manifest.minecraft_version)
+            .await
+        {
+            Ok(v) => v,
+            // Mods can still be installed from the cache, the loader is locked next time
+            Err(e @ (McpmError::Offline { .. } | McpmError::Network { .. }))
+                if manifest.loader_version.is_none() =>
+            {
+                use_io().warn(&format!("Loader version not locked: {}", e));
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+
+        let loader = as_str(&manifest.modloader);
+        let version = match &manifest.loader_version {
+            Some(wanted) => versions.iter().find(|v| &v.version == wanted),
+            None => versions.iter().find(|v| v.stable).or(versions.first()),
+        };
+        let version = version.ok_or_else(|| McpmError::NoCompatibleVersion {
+            slug: loader.clone(),
+            detail: match &manifest.loader_version {
+                Some(wanted) => format!(
+                    "{} {} is not available for Minecraft {}",
+                    loader, wanted, manifest.minecraft_version
+                ),
+                None => format!(
+                    "no {} versions for Minecraft {}",
+                    loader, manifest.minecraft_version
+                ),
+            },
+        })?;
+
+        self.lock.loader = Some(LockedLoader {
+            name: manifest.modloader,
+            version: version.version.clone(),
+            minecraft_version: manifest.minecraft_version.clone(),
+        });
+        Ok(())
     }
 
     // Use this in install and upgrade commands
@@ -126,17 +253,12 @@ impl LockService {
         manifest: &Manifest,
         repo_service: &RepositoryService,
         upgrade: bool,
-    ) -> bool {
-        let mut success = true;
+    ) -> Result<(), McpmError> {
         for m in &manifest.mods_as_entries() {
-            if !self
-                .update_entry(m, manifest, repo_service, None, upgrade)
-                .await
-            {
-                success = false;
-            }
+            self.update_entry(m, manifest, repo_service, None, upgrade)
+                .await?;
         }
-        return success;
+        Ok(())
     }
 
     pub fn get_version(&self, manifest_mod: &ModEntry) -> Option<String> {
@@ -0,0 +1,108 @@
+use clap::ValueEnum;
+use serde::Serialize;
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::app::modules::manifest::models::Manifest;
+
+/// Manifest file names, in the order they are looked for
+pub const MANIFEST_FILES: [&str; 2] = ["mcpm.json", "mcpm.toml"];
+
+/// Top-level tables of `mcpm.toml` that are not part of the manifest and are kept as is
+const FOREIGN_TABLES: [&str; 1] = ["config"];
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestFormat {
+    Json,
+    Toml,
+}
+
+impl ManifestFormat {
+    /// Format of a manifest file, by extension
+    pub fn of(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::Toml,
+            _ => Self::Json,
+        }
+    }
+
+    pub fn file_name(self) -> &'static str {
+        match self {
+            Self::Json => MANIFEST_FILES[0],
+            Self::Toml => MANIFEST_FILES[1],
+        }
+    }
+}
+
+/// Render a manifest as TOML. With `previous`, the content of the file being
+/// replaced, its comments, formatting and key order are kept where the values allow.
+pub fn to_toml(manifest: &Manifest, previous: Option<&str>) -> String {
+    let mut fresh: DocumentMut = toml::to_string(manifest)
+        .expect("manifests serialize to TOML")
+        .parse()
+        .expect("serialized TOML parses");
+    if let Some(Item::Table(mods)) = fresh.get_mut("mods") {
+        // One line per mod: `"modrinth:sodium" = { version = "^0.6", disabled = true }`
+        for (mut key, item) in mods.iter_mut() {
+            if let Item::Table(t) = item {
+                let mut inline = t.clone().into_inline_table();
+                inline.fmt();
+                *item = Item::Value(inline.into());
+                key.fmt();
+            }
+        }
+        mods.sort_values();
+    }
+
+    let Some(mut doc) = previous.and_then(|p| p.parse::<DocumentMut>().ok()) else {
+        return fresh.to_string();
+    };
+
+    let keep: Vec<String> = doc
+        .iter()
+        .map(|(k, _)| k.to_string())
+        .filter(|k| FOREIGN_TABLES.contains(&k.as_str()) && !fresh.contains_key(k))
+        .collect();
+    merge_table(doc.as_table_mut(), fresh.as_table(), &keep);
+    doc.to_string()
+}
+
+/// Make `old` hold the values of `new`, keeping decor of entries that exist in both.
+/// Keys missing from `new` are removed, except those in `keep`.
+fn merge_table(old: &mut Table, new: &Table, keep: &[String]) {
+    let stale: Vec<String> = old
+        .iter()
+        .map(|(k, _)| k.to_string())
+        .filter(|k| !new.contains_key(k) && !keep.contains(k))
+        .collect();
+    for key in stale {
+        old.remove(&key);
+    }
+
+    for (key, item) in new.iter() {
+        match (old.get_mut(key), item) {
+            (Some(Item::Table(old_table)), Item::Table(new_table)) => {
+                merge_table(old_table, new_table, &[]);
+            }
+            (Some(Item::Value(old_value)), Item::Value(new_value)) => {
+                if bare(old_value) != bare(new_value) {
+                    let mut value = new_value.clone();
+                    *value.decor_mut() = old_value.decor().clone();
+                    *old_value = value;
+                }
+            }
+            (Some(old_item), new_item) => *old_item = new_item.clone(),
+            (None, new_item) => {
+                old.insert(key, new_item.clone());
+            }
+        }
+    }
+}
+
+/// A value as written, without the comments and whitespace around it
+fn bare(value: &toml_edit::Value) -> String {
+    let mut value = value.clone();
+    value.decor_mut().clear();
+    value.to_string()
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use crate::app::modules::manifest::{
+        format::to_toml,
+        models::{Channel, Manifest, ModOptions, ModSpec, PartialManifest, VersionSpec},
+    };
+
+    const ANNOTATED: &str = r#"# Our pack
+name = "Pack" # display name
+version = "1.0.0"
+side = "both"
+modloader = "fabric"
+minecraft_version = "1.21.7"
+default_provider = "modrinth"
+
+[config]
+concurrency = 2
+
+[mods]
+# performance
+"modrinth:sodium" = "^0.6" # renderer
+# on its way out
+"modrinth:oldmod" = "1.0.0"
+"#;
+
+    fn manifest_of(content: &str) -> Manifest {
+        toml::from_str::<PartialManifest>(content)
+            .map(Manifest::merge)
+            .unwrap()
+    }
+
+    fn spec(version: &str) -> ModSpec {
+        ModSpec {
+            version: VersionSpec::Range(version.into()),
+            id: None,
+            options: ModOptions::default(),
+        }
+    }
+
+    #[test]
+    fn round_trip_keeps_comments_and_foreign_tables() {
+        let mut manifest = manifest_of(ANNOTATED);
+        manifest.mods.remove("modrinth:oldmod");
+        manifest.mods.insert("modrinth:sodium".into(), spec("^0.7"));
+        manifest
+            .mods
+            .insert("modrinth:lithium".into(), spec("^0.15"));
+
+        let written = to_toml(&manifest, Some(ANNOTATED));
+
+        assert!(written.starts_with("# Our pack\nname = \"Pack\" # display name\n"));
+        assert!(written.contains("[config]\nconcurrency = 2\n"));
+        assert!(written.contains("# performance\n\"modrinth:sodium\" = \"^0.7\" # renderer\n"));
+        assert!(written.contains("\"modrinth:lithium\" = \"^0.15\""));
+        assert!(!written.contains("oldmod"));
+        assert!(!written.contains("on its way out"));
+
+        // Reading it back gives the same manifest
+        let reread = manifest_of(&written);
+        let mut keys: Vec<_> = reread.mods.keys().cloned().collect();
+        keys.sort();
+        assert_eq!(keys, ["modrinth:lithium", "modrinth:sodium"]);
+    }
+
+    #[test]
+    fn options_are_written_inline() {
+        let mut manifest = Manifest::default();
+        manifest.mods.insert(
+            "modrinth:sodium".into(),
+            ModSpec {
+                version: VersionSpec::Range("^0.6".into()),
+                id: None,
+                options: ModOptions {
+                    disabled: true,
+                    ..Default::default()
+                },
+            },
+        );
+
+        let written = to_toml(&manifest, None);
+        assert!(written.contains("\"modrinth:sodium\" = { version = \"^0.6\", disabled = true }"));
+        assert!(
+            manifest_of(&written).mods["modrinth:sodium"]
+                .options
+                .disabled
+        );
+    }
+
+    #[test]
+    fn every_option_round_trips() {
+        let manifest = manifest_of(
+            r#"
+            modloader = "neoforge"
+            loader_version = "21.1.77"
+            [mods]
+            "modrinth:sodium" = { version = "^0.6", id = "AANobbMI", side = "client", optional = true, filename = "sodium.jar", notes = "renderer", version_id = "abc123", channel = "beta" }
+            "#,
+        );
+        let written = to_toml(&manifest, None);
+        let read = manifest_of(&written);
+        let options = &read.mods["modrinth:sodium"].options;
+        assert_eq!(read.loader_version.as_deref(), Some("21.1.77"));
+
+        assert_eq!(options, &manifest.mods["modrinth:sodium"].options);
+        assert!(options.optional);
+        assert_eq!(options.channel, Some(Channel::Beta));
+        assert_eq!(options.version_id.as_deref(), Some("abc123"));
+    }
+}
@@ -1,5 +1,12 @@
+pub mod format;
 pub mod models;
 
 mod services;
 
+pub use format::{ManifestFormat, MANIFEST_FILES};
 pub use services::ManifestService;
+
+#[cfg(test)]
+mod format_spec;
+#[cfg(test)]
+mod schema_spec;
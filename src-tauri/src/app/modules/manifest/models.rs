@@ -1,12 +1,40 @@
 use std::collections::HashMap;
 
 use clap::ValueEnum;
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{
+    de::{self, IgnoredAny, MapAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use strum_macros::{Display, EnumString};
 
-use crate::app::{helpers::semver::is_semver_range, modules::io::use_io};
+use crate::app::{
+    helpers::{
+        schema::Migration,
+        semver::{is_semver_range, VersionRange},
+    },
+    modules::io::use_io,
+    McpmError,
+};
 
-#[derive(Debug, Serialize, Deserialize, Clone, ValueEnum)]
+/// Schema version written by this build, see [`MIGRATIONS`]
+pub const MANIFEST_SCHEMA: u32 = 2;
+
+/// Upgrades from older manifest schemas, in order
+pub const MIGRATIONS: [Migration; 2] = [
+    Migration {
+        from: 0,
+        description: "record the schema version",
+        apply: |_| {},
+    },
+    // Ids are recorded from the lock or the provider as entries are saved
+    Migration {
+        from: 1,
+        description: "mod entries can carry the provider's project id",
+        apply: |_| {},
+    },
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 #[clap(rename_all = "lower")]
 pub enum Side {
@@ -16,15 +44,41 @@ pub enum Side {
     Unknown,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ValueEnum)]
 #[serde(rename_all = "lowercase")]
 pub enum ModLoader {
     Forge,
     Fabric,
     Quilt,
+    #[value(name = "neoforge")]
     NeoForge,
 }
 
+/// Release channel of a mod version, from most to least stable
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    ValueEnum,
+    Display,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+#[clap(rename_all = "lower")]
+pub enum Channel {
+    #[default]
+    Release,
+    Beta,
+    Alpha,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, ValueEnum, Display, EnumString)]
 #[serde(rename_all = "lowercase")]
 #[strum(serialize_all = "lowercase")]
@@ -50,17 +104,17 @@ impl VersionSpec {
             VersionSpec::Range(r) => r,
         }
     }
-    pub fn to_string(&self) -> String {
-        match self {
-            VersionSpec::Exact(v) => v.to_string(),
-            VersionSpec::Range(r) => r.to_string(),
-        }
-    }
     pub fn is_semver_range(&self) -> bool {
         matches!(self, VersionSpec::Range(_))
     }
 }
 
+impl std::fmt::Display for VersionSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl<'de> Deserialize<'de> for VersionSpec {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -68,6 +122,7 @@ impl<'de> Deserialize<'de> for VersionSpec {
     {
         let s = String::deserialize(deserializer)?;
         if is_semver_range(&s) {
+            VersionRange::parse(&s).map_err(de::Error::custom)?;
             Ok(VersionSpec::Range(s))
         } else {
             Ok(VersionSpec::Exact(s))
@@ -75,14 +130,159 @@ impl<'de> Deserialize<'de> for VersionSpec {
     }
 }
 
+/// Per-mod settings beyond the version, only written when set
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ModOptions {
+    /// Side the mod runs on, overriding what the provider says.
+    /// Mods for the other side are left out when installing a client or server pack.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub side: Option<Side>,
+
+    /// Players may leave the mod out, `install` only places it when selected with `--with`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+
+    /// Kept in the manifest and lock, but installed as `.jar.disabled`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+
+    /// Version the entry is frozen at, ignored by upgrades
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<String>,
+
+    /// Why the entry is pinned, shown by `outdated`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pin_reason: Option<String>,
+
+    /// File name in the mods directory instead of `<key>-<version>.jar`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+
+    /// Free text for the pack's maintainers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+
+    /// Provider version id to install, taking precedence over the version spec and pin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+
+    /// Least stable release channel the resolver may pick
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<Channel>,
+}
+
+/// Value of a `mods` entry in the manifest.
+/// Written as a plain version string unless the id or options are set:
+/// `"^1.2"` or `{ "version": "^1.2", "id": "AANobbMI", "disabled": true }`
+#[derive(Debug, Serialize, Clone)]
+#[serde(into = "ModSpecRepr")]
+pub struct ModSpec {
+    pub version: VersionSpec,
+    /// Project id at the provider, which unlike the slug in the key never changes
+    pub id: Option<String>,
+    pub options: ModOptions,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ModSpecRepr {
+    Short(VersionSpec),
+    Full {
+        version: VersionSpec,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        id: Option<String>,
+        #[serde(flatten)]
+        options: ModOptions,
+    },
+}
+
+/// The table form of a `mods` entry. Unknown keys are errors.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ModSpecTable {
+    version: VersionSpec,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(flatten)]
+    options: ModOptions,
+}
+
+/// Hand written rather than untagged, so a mistake inside an entry
+/// is reported as such instead of "did not match any variant"
+impl<'de> Deserialize<'de> for ModSpec {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ModSpecVisitor;
+
+        impl<'de> Visitor<'de> for ModSpecVisitor {
+            type Value = ModSpec;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                f.write_str("a version string or a table with a `version` key")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ModSpec, E> {
+                Ok(ModSpec {
+                    version: VersionSpec::deserialize(de::value::StrDeserializer::new(v))?,
+                    id: None,
+                    options: ModOptions::default(),
+                })
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<ModSpec, A::Error> {
+                let table = ModSpecTable::deserialize(de::value::MapAccessDeserializer::new(map))?;
+                Ok(ModSpec {
+                    version: table.version,
+                    id: table.id,
+                    options: table.options,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(ModSpecVisitor)
+    }
+}
+
+impl From<ModSpec> for ModSpecRepr {
+    fn from(spec: ModSpec) -> Self {
+        if spec.id.is_none() && spec.options == ModOptions::default() {
+            ModSpecRepr::Short(spec.version)
+        } else {
+            ModSpecRepr::Full {
+                version: spec.version,
+                id: spec.id,
+                options: spec.options,
+            }
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ModEntry {
     pub slug: String,
+    /// Project id at the provider, if known. Repository calls use it over the slug.
+    pub id: Option<String>,
     pub version: VersionSpec,
     pub provider: Provider,
+    pub options: ModOptions,
 }
 
 impl ModEntry {
+    /// Whether the version is fixed by a `version_id` or pin, so upgrades leave it alone
+    pub fn is_frozen(&self) -> bool {
+        self.options.version_id.is_some() || self.options.pinned.is_some()
+    }
+
+    /// The version spec the resolver should use, taking a pin into account
+    pub fn effective_version(&self) -> VersionSpec {
+        match &self.options.pinned {
+            Some(v) => VersionSpec::Exact(v.clone()),
+            None => self.version.clone(),
+        }
+    }
+
     pub fn to_key(&self) -> String {
         format!(
             "{}:{}",
@@ -90,53 +290,82 @@ impl ModEntry {
             self.slug.clone()
         )
     }
+
+    /// `provider:id` for repository calls, falling back to the slug while no id is known
+    pub fn project_ref(&self) -> String {
+        format!(
+            "{}:{}",
+            self.provider.to_string().to_lowercase(),
+            self.id.as_deref().unwrap_or(&self.slug)
+        )
+    }
 }
 
 /// Full manifest
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Manifest {
+    pub schema_version: u32,
     pub name: String,
     pub version: String,
     pub description: Option<String>,
     pub author: Option<String>,
     pub side: Side,
     pub modloader: ModLoader,
+    /// Loader version to install, checked against the loader's metadata.
+    /// The newest stable one is locked when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loader_version: Option<String>,
     pub minecraft_version: String,
     pub default_provider: Provider,
-    pub mods: HashMap<String, VersionSpec>,
+    /// Least stable release channel version ranges resolve to, unless a mod sets its own
+    pub channel: Channel,
+    pub mods: HashMap<String, ModSpec>,
     pub license: Option<String>,
     pub homepage: Option<String>,
     pub tags: Option<Vec<String>>,
 }
 
-/// Partial manifest for merging
+/// Partial manifest for merging. Unknown keys are errors.
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(deny_unknown_fields)]
 pub struct PartialManifest {
+    pub schema_version: Option<u32>,
     pub name: Option<String>,
     pub version: Option<String>,
     pub description: Option<String>,
     pub author: Option<String>,
     pub side: Option<Side>,
     pub modloader: Option<ModLoader>,
+    pub loader_version: Option<String>,
     pub minecraft_version: Option<String>,
     pub default_provider: Option<Provider>,
-    pub mods: Option<HashMap<String, VersionSpec>>,
+    pub channel: Option<Channel>,
+    #[serde(default, deserialize_with = "deserialize_mods")]
+    pub mods: Option<HashMap<String, ModSpec>>,
     pub license: Option<String>,
     pub homepage: Option<String>,
     pub tags: Option<Vec<String>>,
+    /// The `[config]` table of `mcpm.toml`, read by [`crate::app::Config`].
+    /// JSON manifests are rejected when they have one.
+    #[serde(default, skip_serializing)]
+    pub config: Option<IgnoredAny>,
 }
 
 impl Default for Manifest {
     fn default() -> Self {
         Self {
+            schema_version: MANIFEST_SCHEMA,
             name: "My Modpack".to_string(),
             version: "1.0.0".to_string(),
             description: Some("A Minecraft modpack".to_string()),
             author: None,
             side: Side::Both,
             modloader: ModLoader::Fabric,
+            loader_version: None,
+            // Only used when Mojang's version list can't be read, see `mcpm init`
             minecraft_version: "1.21.7".to_string(),
             default_provider: Provider::Modrinth,
+            channel: Channel::Release,
             mods: HashMap::new(),
             license: None,
             homepage: None,
@@ -146,23 +375,26 @@ impl Default for Manifest {
 }
 
 impl Manifest {
-    /// Merge a partial manifest with defaults
+    /// Merge a partial manifest, already migrated to [`MANIFEST_SCHEMA`], with defaults
     pub fn merge(partial: PartialManifest) -> Self {
         let defaults = Manifest::default();
 
         Manifest {
+            schema_version: defaults.schema_version,
             name: partial.name.unwrap_or(defaults.name),
             version: partial.version.unwrap_or(defaults.version),
             description: partial.description.or(defaults.description),
             author: partial.author.or(defaults.author),
             side: partial.side.unwrap_or(defaults.side),
             modloader: partial.modloader.unwrap_or(defaults.modloader),
+            loader_version: partial.loader_version.or(defaults.loader_version),
             minecraft_version: partial
                 .minecraft_version
                 .unwrap_or(defaults.minecraft_version),
             default_provider: partial
                 .default_provider
                 .unwrap_or(defaults.default_provider),
+            channel: partial.channel.unwrap_or(defaults.channel),
             mods: partial.mods.unwrap_or(defaults.mods),
             license: partial.license.or(defaults.license),
             homepage: partial.homepage.or(defaults.homepage),
@@ -170,29 +402,24 @@ impl Manifest {
         }
     }
 
-    // TODO: Revert slug exclusive change. Use slug for user interaction but save the id in case the slug changes.
+    /// Least stable release channel the resolver may pick for `entry`
+    pub fn channel_for(&self, entry: &ModEntry) -> Channel {
+        entry.options.channel.unwrap_or(self.channel)
+    }
 
     pub fn mods_as_entries(&self) -> Vec<ModEntry> {
         self.mods
             .iter()
-            .map(|(key, version)| {
-                // split key into provider + slug
-                let mut parts = key.splitn(2, ':');
-                let provider_str = parts.next().unwrap_or_default();
-                let slug = parts.next().unwrap_or_default().to_string();
-
-                let provider = match provider_str {
-                    "modrinth" => Provider::Modrinth,
-                    "curseforge" => Provider::CurseForge,
-                    "github" => Provider::Github,
-                    "maven" => Provider::Maven,
-                    _ => self.default_provider.clone(),
-                };
+            .map(|(key, spec)| {
+                let (provider, slug) =
+                    split_mod_key(key).expect("mod keys are checked when the manifest is read");
 
                 ModEntry {
-                    slug: slug.clone(),
-                    version: version.clone(),
+                    slug: slug.to_string(),
+                    id: spec.id.clone(),
+                    version: spec.version.clone(),
                     provider,
+                    options: spec.options.clone(),
                 }
             })
             .collect()
@@ -200,64 +427,102 @@ impl Manifest {
 
     pub fn insert_mod_entry(&mut self, entry: &ModEntry) {
         let key = entry.to_key();
-        self.mods.insert(key, entry.version.clone());
+        self.mods.insert(
+            key,
+            ModSpec {
+                version: entry.version.clone(),
+                id: entry.id.clone(),
+                options: entry.options.clone(),
+            },
+        );
+    }
+
+    pub fn get_mod(&self, provider: &Provider, slug: &str) -> Option<&ModSpec> {
+        self.mods.get(&format!("{}:{}", provider, slug))
     }
 
-    /// Remove a mod by ID or slug, returns true if removed
-    pub fn remove_mod_entry(&mut self, provider: &Provider, slug: &str) -> bool {
-        let io = use_io();
+    pub fn get_mod_mut(
+        &mut self,
+        provider: &Provider,
+        slug: &str,
+    ) -> Result<&mut ModSpec, McpmError> {
+        self.mods
+            .get_mut(&format!("{}:{}", provider, slug))
+            .ok_or_else(|| McpmError::NotFound(format!("Mod '{}'", slug)))
+    }
 
+    /// Remove a mod by ID or slug
+    pub fn remove_mod_entry(&mut self, provider: &Provider, slug: &str) -> Result<(), McpmError> {
         let key = format!("{}:{}", provider, slug);
-        if self.mods.remove(&key).is_some() {
-            io.success(&format!("Removed {}", key));
-            true
-        } else {
-            io.warn(&format!("No entry found for {}", key));
-            false
+        self.mods
+            .remove(&key)
+            .ok_or_else(|| McpmError::NotFound(format!("Mod '{}'", slug)))?;
+        use_io().success(&format!("Removed {}", key));
+        Ok(())
+    }
+}
+
+/// Split a `mods` key into its provider and slug
+pub fn split_mod_key(key: &str) -> Result<(Provider, &str), String> {
+    let (provider, slug) = key
+        .split_once(':')
+        .filter(|(_, slug)| !slug.is_empty())
+        .ok_or_else(|| format!("mod key '{}' is not `provider:slug`", key))?;
+    let provider = provider.parse().map_err(|_| {
+        format!(
+            "unknown provider '{}' in mod key '{}', expected one of {}",
+            provider,
+            key,
+            Provider::value_variants()
+                .iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    })?;
+    Ok((provider, slug))
+}
+
+/// Reads `mods` checking each key as it comes, so a bad one is reported where it is
+fn deserialize_mods<'de, D>(deserializer: D) -> Result<Option<HashMap<String, ModSpec>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct ModKey(String);
+
+    impl<'de> Deserialize<'de> for ModKey {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let key = String::deserialize(deserializer)?;
+            split_mod_key(&key).map_err(de::Error::custom)?;
+            Ok(ModKey(key))
         }
     }
+
+    struct ModsVisitor;
+
+    impl<'de> Visitor<'de> for ModsVisitor {
+        type Value = HashMap<String, ModSpec>;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            f.write_str("a table of mods by `provider:slug`")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut mods = HashMap::new();
+            while let Some(ModKey(key)) = map.next_key()? {
+                mods.insert(key, map.next_value()?);
+            }
+            Ok(mods)
+        }
+    }
+
+    deserializer.deserialize_map(ModsVisitor).map(Some)
 }
 
 impl PartialManifest {
-    /// Extract a PartialManifest safely from any JSON value
-    pub fn from_value(value: serde_json::Value) -> Self {
-        Self {
-            name: value
-                .get("name")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
-            version: value
-                .get("version")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
-            description: value
-                .get("description")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
-            author: value
-                .get("author")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
-            side: value
-                .get("side")
-                .and_then(|v| serde_json::from_value(v.clone()).ok()),
-            modloader: value
-                .get("modloader")
-                .and_then(|v| serde_json::from_value(v.clone()).ok()),
-            minecraft_version: value
-                .get("minecraft_version")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
-            default_provider: value
-                .get("default_provider")
-                .and_then(|v| serde_json::from_value(v.clone()).ok()),
-            mods: value
-                .get("mods")
-                .and_then(|v| serde_json::from_value(v.clone()).ok()),
-            license: value
-                .get("license")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
-            homepage: value
-                .get("homepage")
-                .and_then(|v| v.as_str().map(|s| s.to_string())),
-            tags: value
-                .get("tags")
-                .and_then(|v| serde_json::from_value(v.clone()).ok()),
-        }
+    /// Read a manifest already converted to JSON. Unlike parsing the file
+    /// directly, errors carry no line and column.
+    pub fn from_value(value: serde_json::Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value(value)
     }
 }
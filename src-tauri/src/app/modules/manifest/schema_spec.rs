@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use serde_json::json;
+    use tempfile::tempdir;
+
+    use crate::app::{
+        helpers::schema::migrate,
+        modules::manifest::{
+            models::{Manifest, PartialManifest, Provider, MANIFEST_SCHEMA, MIGRATIONS},
+            ManifestService,
+        },
+    };
+
+    #[test]
+    fn unknown_keys_are_errors_with_a_location() {
+        let err = serde_json::from_str::<PartialManifest>(
+            "{\n  \"name\": \"Pack\",\n  \"modlaoder\": \"fabric\"\n}",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown field `modlaoder`"));
+        assert_eq!(err.line(), 3);
+
+        let err = toml::from_str::<PartialManifest>("name = \"Pack\"\nmodlaoder = \"fabric\"\n")
+            .unwrap_err();
+        assert!(err.message().contains("unknown field `modlaoder`"));
+        assert!(err.to_string().contains("line 2"));
+
+        // Options of a mod entry are checked too
+        let err = toml::from_str::<PartialManifest>(
+            "[mods]\n\"modrinth:sodium\" = { version = \"^0.6\", disabeld = true }\n",
+        )
+        .unwrap_err();
+        assert!(err.message().contains("unknown field `disabeld`"));
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn invalid_enum_values_are_errors() {
+        let err =
+            serde_json::from_str::<PartialManifest>("{\"modloader\": \"fabirc\"}").unwrap_err();
+        assert!(err.to_string().contains("unknown variant `fabirc`"));
+        assert_eq!((err.line(), err.column()), (1, 22));
+
+        // The project config of mcpm.toml is not part of the manifest
+        assert!(toml::from_str::<PartialManifest>("[config]\nconcurrency = 2\n").is_ok());
+    }
+
+    #[test]
+    fn older_manifests_are_migrated_step_by_step() {
+        let mut value = json!({ "name": "Pack", "mods": { "modrinth:sodium": "^0.6" } });
+        let report = migrate(&mut value, MANIFEST_SCHEMA, &MIGRATIONS).unwrap();
+        assert_eq!((report.from, report.to), (0, MANIFEST_SCHEMA));
+        assert_eq!(report.steps.len(), MANIFEST_SCHEMA as usize);
+        assert_eq!(value["schema_version"], MANIFEST_SCHEMA);
+        assert!(PartialManifest::from_value(value.clone()).is_ok());
+
+        // Nothing to do the second time
+        assert!(migrate(&mut value, MANIFEST_SCHEMA, &MIGRATIONS)
+            .unwrap()
+            .is_empty());
+
+        let mut newer = json!({ "schema_version": MANIFEST_SCHEMA + 1 });
+        let err = migrate(&mut newer, MANIFEST_SCHEMA, &MIGRATIONS).unwrap_err();
+        assert!(err.contains("newer than this mcpm supports"));
+    }
+
+    #[test]
+    fn mod_entries_carry_the_project_id() {
+        let partial = serde_json::from_str::<PartialManifest>(
+            r#"{ "mods": {
+                "modrinth:sodium": { "version": "^0.6", "id": "AANobbMI" },
+                "modrinth:lithium": "^0.15"
+            } }"#,
+        )
+        .unwrap();
+        let mut manifest = Manifest::merge(partial);
+
+        let mut entries = manifest.mods_as_entries();
+        entries.sort_by_key(|e| e.to_key());
+        assert_eq!(entries[0].project_ref(), "modrinth:lithium");
+        assert_eq!(entries[1].project_ref(), "modrinth:AANobbMI");
+
+        // The shorthand is kept until an id is known
+        let written = serde_json::to_value(&manifest).unwrap();
+        assert_eq!(written["mods"]["modrinth:lithium"], "^0.15");
+        assert_eq!(written["mods"]["modrinth:sodium"]["id"], "AANobbMI");
+
+        let mut lithium = entries.remove(0);
+        lithium.id = Some("gvQqBUqZ".into());
+        manifest.insert_mod_entry(&lithium);
+        assert_eq!(
+            manifest
+                .get_mod(&Provider::Modrinth, "lithium")
+                .unwrap()
+                .id
+                .as_deref(),
+            Some("gvQqBUqZ")
+        );
+    }
+
+    #[test]
+    fn missing_entries_exit_with_not_found() {
+        // toggle, unpin and remove fail instead of exiting 0 with a warning
+        let mut manifest = Manifest::default();
+        let err = manifest
+            .get_mod_mut(&Provider::Modrinth, "sodium")
+            .unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+        assert_eq!(err.to_string(), "Mod 'sodium' not found");
+
+        let err = manifest
+            .remove_mod_entry(&Provider::Modrinth, "sodium")
+            .unwrap_err();
+        assert_eq!(err.exit_code(), 3);
+    }
+
+    #[test]
+    fn mod_keys_need_a_known_provider() {
+        let err = serde_json::from_str::<PartialManifest>(
+            "{\n  \"mods\": {\n    \"modrinth:sodium\": \"^0.6\",\n    \"curseforg:jei\": \"1.0\"\n  }\n}",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown provider 'curseforg'"));
+        assert_eq!(err.line(), 4);
+
+        let err = toml::from_str::<PartialManifest>("[mods]\nsodium = \"^0.6\"").unwrap_err();
+        assert!(err.to_string().contains("not `provider:slug`"));
+    }
+
+    #[test]
+    fn json_manifests_have_no_config_table() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mcpm.json");
+        fs::write(
+            &path,
+            "{\n  \"schema_version\": 2,\n  \"config\": { \"offline\": true }\n}",
+        )
+        .unwrap();
+        let err = ManifestService::at(path).load().unwrap_err();
+        assert_eq!(err.exit_code(), 7);
+        assert!(err.causes()[0].contains("line 3"));
+    }
+}
@@ -1,83 +1,170 @@
+use serde::{de, Deserialize, Deserializer};
 use serde_json::Value;
+use std::error::Error;
 use std::fs;
 use std::io;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::app::helpers::schema::migrate;
 use crate::app::modules::io::use_io;
-use crate::app::modules::manifest::models::{Manifest, PartialManifest};
+use crate::app::modules::manifest::format::{to_toml, ManifestFormat};
+use crate::app::modules::manifest::models::{
+    Manifest, PartialManifest, MANIFEST_SCHEMA, MIGRATIONS,
+};
+use crate::app::{Config, McpmError};
 
-const MANIFEST_FILE: &str = "mcpm.json";
 const GITIGNORE_FILE: &str = ".gitignore";
-const RECOMMENDED_IGNORES: [&str; 4] = ["mods/", "crash-reports/", "logs/", "saves/"];
+const RECOMMENDED_IGNORES: [&str; 5] = ["mods/", "crash-reports/", "logs/", "saves/", ".mcpm/"];
 
-pub struct ManifestService;
+pub struct ManifestService {
+    path: PathBuf,
+}
+
+impl Default for ManifestService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl ManifestService {
+    /// The project's manifest, see [`Config::manifest_path`]
     pub fn new() -> Self {
-        Self
+        Self::at(Config::get().manifest_path.clone())
     }
 
-    /// Initialize a manifest (create or normalize) and handle .gitignore
-    pub fn init(&self) -> std::io::Result<()> {
-        let manifest_path = Path::new(MANIFEST_FILE);
-        let gitignore_path = Path::new(GITIGNORE_FILE);
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
 
-        if !manifest_path.exists() {
-            self.create()?;
+    pub fn format(&self) -> ManifestFormat {
+        ManifestFormat::of(&self.path)
+    }
+
+    /// Initialize a manifest (create or normalize) and handle .gitignore
+    pub fn init(&self, minecraft_version: Option<String>) -> Result<(), McpmError> {
+        let io_error = |e| McpmError::io("Initialization failed", e);
+        if !self.path.exists() {
+            self.create(minecraft_version).map_err(io_error)?;
         } else {
-            self.normalize(manifest_path)?;
+            self.normalize()?;
         }
 
-        self.init_gitignore(gitignore_path)?;
+        let gitignore_path = self.path.with_file_name(GITIGNORE_FILE);
+        self.init_gitignore(&gitignore_path).map_err(io_error)?;
 
         Ok(())
     }
 
     /// Load manifest from disk.  
-    /// - If file missing: returns Err(io::ErrorKind::NotFound).  
-    /// - If malformed: returns Err(io::ErrorKind::InvalidData).  
+    /// - If file missing: returns Err(McpmError::NotFound).  
+    /// - If malformed, with unknown keys or invalid values: returns Err(McpmError::ManifestParse).  
+    /// - If written by an older schema: migrates it in memory and reports the steps.  
     /// - If partial: normalizes into full Manifest.  
-    pub fn load(&self) -> io::Result<Manifest> {
-        let path = Path::new(MANIFEST_FILE);
-
-        let content = fs::read_to_string(path)?;
-        let value: Value = serde_json::from_str(&content)
-            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "Invalid JSON"))?;
+    pub fn load(&self) -> Result<Manifest, McpmError> {
+        let path = &self.path;
+
+        let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => McpmError::NotFound(format!("Manifest {}", path.display())),
+            _ => McpmError::io(format!("Failed to read {}", path.display()), e),
+        })?;
+        let parse_error = |source| McpmError::ManifestParse {
+            path: path.to_path_buf(),
+            source,
+        };
+
+        let mut value = self.parse(&content).map_err(parse_error)?;
+        if self.format() == ManifestFormat::Json {
+            reject_config(&content).map_err(|e| parse_error(e.into()))?;
+        }
+        let report =
+            migrate(&mut value, MANIFEST_SCHEMA, &MIGRATIONS).map_err(|e| parse_error(e.into()))?;
 
-        let partial = PartialManifest::from_value(value);
+        let partial = if report.is_empty() {
+            // Straight from the text, so errors point at a line and column
+            self.parse_partial(&content)
+        } else {
+            PartialManifest::from_value(value).map_err(|e| {
+                // Point at the original text when it has the same mistake
+                match self.parse_partial(&content) {
+                    Err(located) if located.to_string().contains(&e.to_string()) => located,
+                    _ => e.into(),
+                }
+            })
+        }
+        .map_err(parse_error)?;
+
+        if !report.is_empty() {
+            use_io().info(&format!(
+                "Upgraded {} from schema {} to {}: {}. It is saved by the next install or change, or now with `mcpm init`",
+                path.display(),
+                report.from,
+                report.to,
+                report.steps.join("; ")
+            ));
+        }
         Ok(Manifest::merge(partial))
     }
 
-    /// Save the manifest to disk
+    /// Save the manifest to disk. A TOML manifest keeps its comments and layout.
     pub fn save(&self, manifest: &Manifest) -> std::io::Result<()> {
-        let json = serde_json::to_string_pretty(manifest).unwrap();
-        std::fs::write(MANIFEST_FILE, json)?;
+        let content = match self.format() {
+            ManifestFormat::Json => serde_json::to_string_pretty(manifest).unwrap(),
+            ManifestFormat::Toml => {
+                let previous = fs::read_to_string(&self.path).ok();
+                to_toml(manifest, previous.as_deref())
+            }
+        };
+        std::fs::write(&self.path, content)?;
         Ok(())
     }
 
+    /// Parse manifest content into a JSON value, whatever the file format
+    fn parse(&self, content: &str) -> Result<Value, Box<dyn Error + Send + Sync>> {
+        Ok(match self.format() {
+            ManifestFormat::Json => serde_json::from_str(content)?,
+            ManifestFormat::Toml => serde_json::to_value(toml::from_str::<toml::Table>(content)?)?,
+        })
+    }
+
+    /// Parse manifest content of the current schema, rejecting unknown keys and invalid values
+    fn parse_partial(
+        &self,
+        content: &str,
+    ) -> Result<PartialManifest, Box<dyn Error + Send + Sync>> {
+        Ok(match self.format() {
+            ManifestFormat::Json => serde_json::from_str(content)?,
+            ManifestFormat::Toml => toml::from_str(content)?,
+        })
+    }
+
     /// Create a new manifest with defaults
-    pub fn create(&self) -> std::io::Result<()> {
+    pub fn create(&self, minecraft_version: Option<String>) -> std::io::Result<()> {
         let io = use_io();
 
-        let manifest = Manifest::default();
+        let defaults = Manifest::default();
+        let manifest = Manifest {
+            default_provider: Config::get().default_provider.clone(),
+            minecraft_version: minecraft_version.unwrap_or(defaults.minecraft_version),
+            ..defaults
+        };
         self.save(&manifest)?;
-        io.success(&format!("Created {}", MANIFEST_FILE));
+        io.success(&format!("Created {}", self.path.display()));
         Ok(())
     }
 
-    fn normalize(&self, path: &Path) -> std::io::Result<()> {
+    /// Rewrite an existing manifest with defaults filled in and at the current schema
+    fn normalize(&self) -> Result<(), McpmError> {
         let io = use_io();
 
-        let content = fs::read_to_string(path)?;
-        let value: serde_json::Value = serde_json::from_str(&content).unwrap_or_default();
-
-        let partial = PartialManifest::from_value(value);
-        let manifest = Manifest::merge(partial);
-
-        let json = serde_json::to_string_pretty(&manifest).unwrap();
-        fs::write(path, json)?;
-        io.info(&format!("Normalized existing {}", MANIFEST_FILE));
+        let manifest = self.load()?;
+        self.save(&manifest)
+            .map_err(|e| McpmError::io(format!("Failed to write {}", self.path.display()), e))?;
+        io.info(&format!("Normalized existing {}", self.path.display()));
         Ok(())
     }
 
@@ -106,3 +193,21 @@ impl ManifestService {
         Ok(())
     }
 }
+
+/// Only `mcpm.toml` has a `[config]` table, `mcpm.json` projects use `.mcpmrc`.
+/// Read from the text so the error points at the key.
+fn reject_config(content: &str) -> Result<(), serde_json::Error> {
+    #[derive(Deserialize)]
+    struct TopLevel {
+        #[serde(rename = "config", default, deserialize_with = "config_key")]
+        _config: (),
+    }
+
+    fn config_key<'de, D: Deserializer<'de>>(_: D) -> Result<(), D::Error> {
+        Err(de::Error::custom(
+            "`config` is only read from mcpm.toml, put settings for mcpm.json in .mcpmrc",
+        ))
+    }
+
+    serde_json::from_str::<TopLevel>(content).map(|_| ())
+}
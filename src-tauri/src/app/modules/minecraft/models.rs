@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Mojang's `version_manifest_v2.json`, every Minecraft version newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionManifest {
+    pub latest: Latest,
+    pub versions: Vec<GameVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Latest {
+    pub release: String,
+    pub snapshot: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameVersion {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: VersionKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionKind {
+    Release,
+    Snapshot,
+    OldBeta,
+    OldAlpha,
+}
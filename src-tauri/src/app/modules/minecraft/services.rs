@@ -0,0 +1,155 @@
+use std::{fs, path::PathBuf};
+
+use tokio::sync::OnceCell;
+
+use super::models::{VersionKind, VersionManifest};
+use crate::app::{helpers::http, modules::io::use_io, Config, McpmError};
+
+/// Default base URL, overridden by `mirrors.mojang`
+const MOJANG_META: &str = "https://piston-meta.mojang.com";
+const MANIFEST_PATH: &str = "/mc/game/version_manifest_v2.json";
+const CACHE_FILE: &str = "version_manifest_v2.json";
+
+/// Read once per run: fetched when online, otherwise from the cache
+static VERSIONS: OnceCell<Option<VersionManifest>> = OnceCell::const_new();
+
+/// Minecraft versions from Mojang's version manifest
+pub struct MinecraftService;
+
+impl MinecraftService {
+    /// The version manifest, freshly fetched or from the cache when that fails or
+    /// offline mode is on. `None` when neither works, callers then skip their checks.
+    pub async fn versions() -> Option<&'static VersionManifest> {
+        VERSIONS.get_or_init(Self::load).await.as_ref()
+    }
+
+    /// Newest release, or the newest snapshot with `snapshot`
+    pub async fn latest(snapshot: bool) -> Option<String> {
+        let latest = &Self::versions().await?.latest;
+        Some(if snapshot {
+            latest.snapshot.clone()
+        } else {
+            latest.release.clone()
+        })
+    }
+
+    /// Fail on versions Mojang doesn't know, suggesting close ones, and on
+    /// snapshots unless `allow_snapshot`. Passes when the manifest can't be had.
+    pub async fn validate(id: &str, allow_snapshot: bool) -> Result<(), McpmError> {
+        match Self::versions().await {
+            Some(manifest) => check(manifest, id, allow_snapshot),
+            None => Ok(()),
+        }
+    }
+
+    async fn load() -> Option<VersionManifest> {
+        let cache = cache_path();
+        let url = format!("{}{}", base_url(), MANIFEST_PATH);
+
+        match fetch(&url).await {
+            Ok(body) => {
+                let parsed = serde_json::from_str(&body).ok();
+                if parsed.is_some() {
+                    // Failing to cache only costs the offline fallback
+                    let _ = fs::create_dir_all(&Config::get().cache_dir)
+                        .and_then(|_| fs::write(&cache, &body));
+                }
+                parsed
+            }
+            Err(e) => {
+                let cached = fs::read_to_string(&cache)
+                    .ok()
+                    .and_then(|c| serde_json::from_str(&c).ok());
+                if cached.is_none() {
+                    use_io().debug(&format!("Minecraft versions not checked: {}", e));
+                }
+                cached
+            }
+        }
+    }
+}
+
+/// See [`MinecraftService::validate`]
+pub(super) fn check(
+    manifest: &VersionManifest,
+    id: &str,
+    allow_snapshot: bool,
+) -> Result<(), McpmError> {
+    match manifest.versions.iter().find(|v| v.id == id) {
+        Some(v) if v.kind == VersionKind::Release || allow_snapshot => Ok(()),
+        Some(_) => Err(McpmError::InvalidInput(format!(
+            "Minecraft {} is a snapshot, pass --snapshot to use it",
+            id
+        ))),
+        None => {
+            let suggestions = suggestions(manifest, id);
+            Err(McpmError::InvalidInput(if suggestions.is_empty() {
+                format!("Unknown Minecraft version '{}'", id)
+            } else {
+                format!(
+                    "Unknown Minecraft version '{}', did you mean {}?",
+                    id,
+                    suggestions.join(", ")
+                )
+            }))
+        }
+    }
+}
+
+/// Up to three versions within two edits of `id`, closest and releases first
+pub(super) fn suggestions(manifest: &VersionManifest, id: &str) -> Vec<String> {
+    let mut close: Vec<_> = manifest
+        .versions
+        .iter()
+        .map(|v| (edit_distance(&v.id, id), v.kind != VersionKind::Release, v))
+        .filter(|(d, _, _)| *d <= 2)
+        .collect();
+    // Stable, so equally close versions stay newest first
+    close.sort_by_key(|(d, snapshot, _)| (*d, *snapshot));
+    close
+        .into_iter()
+        .take(3)
+        .map(|(_, _, v)| v.id.clone())
+        .collect()
+}
+
+/// Levenshtein distance
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+async fn fetch(url: &str) -> Result<String, McpmError> {
+    http::ensure_online(url)?;
+    http::client()
+        .get(url)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| McpmError::network(url, e))?
+        .text()
+        .await
+        .map_err(|e| McpmError::network(url, e))
+}
+
+fn base_url() -> String {
+    Config::get()
+        .mirrors
+        .get("mojang")
+        .map(|m| m.trim_end_matches('/').to_string())
+        .unwrap_or_else(|| MOJANG_META.to_string())
+}
+
+fn cache_path() -> PathBuf {
+    Config::get().cache_dir.join(CACHE_FILE)
+}
@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    use crate::app::{
+        modules::minecraft::{
+            models::VersionManifest,
+            services::{check, suggestions},
+        },
+        McpmError,
+    };
+
+    fn manifest() -> VersionManifest {
+        serde_json::from_value(serde_json::json!({
+            "latest": { "release": "1.21.8", "snapshot": "25w31a" },
+            "versions": [
+                { "id": "25w31a", "type": "snapshot", "url": "", "time": "", "releaseTime": "" },
+                { "id": "1.21.8", "type": "release" },
+                { "id": "1.21.7", "type": "release" },
+                { "id": "1.21.7-rc1", "type": "snapshot" },
+                { "id": "1.21.1", "type": "release" },
+                { "id": "1.20.1", "type": "release" },
+                { "id": "b1.7.3", "type": "old_beta" }
+            ]
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn typos_get_suggestions() {
+        let manifest = manifest();
+        assert!(check(&manifest, "1.21.7", false).is_ok());
+
+        assert_eq!(
+            suggestions(&manifest, "1.21.77"),
+            vec!["1.21.7", "1.21.8", "1.21.1"]
+        );
+        let err = check(&manifest, "1.2.17", false).unwrap_err().to_string();
+        assert!(
+            err.starts_with("Unknown Minecraft version '1.2.17', did you mean 1.21.7"),
+            "{err}"
+        );
+        assert_eq!(
+            check(&manifest, "banana", false).unwrap_err().to_string(),
+            "Unknown Minecraft version 'banana'"
+        );
+    }
+
+    #[test]
+    fn snapshots_need_to_be_allowed() {
+        let manifest = manifest();
+        assert!(matches!(
+            check(&manifest, "25w31a", false),
+            Err(McpmError::InvalidInput(_))
+        ));
+        assert!(check(&manifest, "25w31a", true).is_ok());
+        assert!(check(&manifest, "b1.7.3", true).is_ok());
+    }
+}
@@ -1,5 +1,8 @@
 pub mod core;
 pub mod io;
+pub mod loaders;
 pub mod lock;
 pub mod manifest;
+pub mod minecraft;
 pub mod repositories;
+pub mod snapshot;
@@ -1,16 +1,19 @@
 use super::models::{ModResult, VersionResult};
+use crate::app::McpmError;
 
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait IRepository: Send + Sync {
-    async fn search(&self, query: &str, page: usize) -> Vec<ModResult>;
-    async fn find(&self, slug: &str) -> Option<ModResult>;
+    async fn search(&self, query: &str, page: usize) -> Result<Vec<ModResult>, McpmError>;
+
+    /// Look a project up by slug or project id. `Ok(None)` if the provider does not know it
+    async fn find(&self, slug: &str) -> Result<Option<ModResult>, McpmError>;
 
     async fn get_versions(
         &self,
         project_id: &str,
         game_versions: &[String],
         loaders: &[String],
-    ) -> Vec<VersionResult>;
+    ) -> Result<Vec<VersionResult>, McpmError>;
 }
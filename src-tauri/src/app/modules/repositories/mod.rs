@@ -7,4 +7,5 @@ pub mod modrinth;
 mod interfaces;
 mod services;
 
+pub use interfaces::IRepository;
 pub use services::RepositoryService;
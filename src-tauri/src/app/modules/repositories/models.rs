@@ -1,4 +1,6 @@
-use crate::app::modules::manifest::models::Side;
+use chrono::{DateTime, Utc};
+
+use crate::app::modules::manifest::models::{Channel, Side};
 
 #[derive(Debug, Clone, serde::Serialize)]
 pub struct ModResult {
@@ -11,11 +13,16 @@ pub struct ModResult {
     pub url: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct VersionResult {
     pub mod_id: String,
+    pub version_id: String,              // provider id of this version
     pub version: String,                 // e.g. "15.2.1"
+    pub version_type: Channel,           // release, beta or alpha
     pub minecraft_versions: Vec<String>, // ["1.21.7", "1.21.6"]
     pub url: String,                     // direct download or project version page
     pub hash: String,
+    pub size: Option<u64>,
+    pub dependencies: Vec<String>, // project ids of required dependencies
+    pub published: Option<DateTime<Utc>>,
 }
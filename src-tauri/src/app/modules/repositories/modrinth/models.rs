@@ -2,6 +2,8 @@
 
 use serde::Deserialize;
 
+use crate::app::modules::manifest::models::Channel;
+
 #[derive(Deserialize)]
 pub struct SearchResponse {
     pub hits: Vec<SearchItem>,
@@ -50,6 +52,7 @@ pub struct VersionItem {
     pub author_id: String,
     pub date_published: String,
     pub version_number: String,
+    pub version_type: Channel,
     pub game_versions: Vec<String>,
     pub loaders: Vec<String>,
     pub files: Vec<VersionFile>,
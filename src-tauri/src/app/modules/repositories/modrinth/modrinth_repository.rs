@@ -2,6 +2,7 @@ use super::super::interfaces::IRepository;
 use super::super::models::ModResult;
 use super::models::SearchResponse;
 use crate::app::{
+    helpers::http,
     modules::{
         manifest::models::Side,
         repositories::{
@@ -9,42 +10,85 @@ use crate::app::{
             modrinth::models::{FindResponse, VersionItem},
         },
     },
-    PAGINATION_SIZE,
+    Config, McpmError, PAGINATION_SIZE,
 };
 use async_trait::async_trait;
-use reqwest::Client;
+use chrono::{DateTime, Utc};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use serde::de::DeserializeOwned;
+
+/// Default API base URL, overridden by `mirrors.modrinth`
+const API_URL: &str = "https://api.modrinth.com/v2";
 
 pub struct ModrinthRepository {
     client: Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl Default for ModrinthRepository {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl ModrinthRepository {
     pub fn new() -> Self {
+        let config = Config::get();
         Self {
-            client: Client::new(),
+            client: http::client(),
+            base_url: config
+                .mirrors
+                .get("modrinth")
+                .map(|m| m.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| API_URL.to_string()),
+            api_key: config.api_keys.get("modrinth").cloned(),
+        }
+    }
+
+    /// GET and decode a JSON response, `Ok(None)` on 404
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<Option<T>, McpmError> {
+        http::ensure_online(url)?;
+
+        let mut request = self.client.get(url);
+        if let Some(key) = &self.api_key {
+            request = request.header(AUTHORIZATION, key);
+        }
+        let res = request
+            .send()
+            .await
+            .map_err(|e| McpmError::network(url, e))?;
+
+        if res.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
         }
+
+        res.error_for_status()
+            .map_err(|e| McpmError::network(url, e))?
+            .json()
+            .await
+            .map(Some)
+            .map_err(|e| {
+                if e.is_decode() {
+                    McpmError::provider_response(url, e)
+                } else {
+                    McpmError::network(url, e)
+                }
+            })
     }
 }
 
 #[async_trait]
 impl IRepository for ModrinthRepository {
-    async fn search(&self, query: &str, page: usize) -> Vec<ModResult> {
-        let url =
-            format!("https://api.modrinth.com/v2/search?query={query}") + &with_pagination(page);
-
-        // Send request
-        let res = match self.client.get(&url).send().await {
-            Ok(r) => r,
-            Err(_) => return vec![],
-        };
+    async fn search(&self, query: &str, page: usize) -> Result<Vec<ModResult>, McpmError> {
+        let url = format!("{}/search?query={query}", self.base_url) + &with_pagination(page);
 
-        // Parse JSON
-        let parsed: SearchResponse = match res.json().await {
-            Ok(p) => p,
-            Err(_) => return vec![],
-        };
+        let parsed: SearchResponse = self
+            .get_json(&url)
+            .await?
+            .ok_or_else(|| McpmError::NotFound(url.clone()))?;
 
-        parsed
+        Ok(parsed
             .hits
             .into_iter()
             .map(|hit| ModResult {
@@ -56,25 +100,17 @@ impl IRepository for ModrinthRepository {
                 side: get_side(&hit.client_side, &hit.server_side),
                 url: format!("https://modrinth.com/mod/{}", hit.slug.clone()),
             })
-            .collect()
+            .collect())
     }
 
-    async fn find(&self, slug: &str) -> Option<ModResult> {
-        let url = format!("https://api.modrinth.com/v2/project/{slug}");
-
-        // Send request
-        let res = match self.client.get(&url).send().await {
-            Ok(r) => r,
-            Err(_) => return None,
-        };
+    async fn find(&self, slug: &str) -> Result<Option<ModResult>, McpmError> {
+        let url = format!("{}/project/{slug}", self.base_url);
 
-        // Parse JSON
-        let response: FindResponse = match res.json().await {
-            Ok(p) => p,
-            Err(_) => return None,
+        let Some(response) = self.get_json::<FindResponse>(&url).await? else {
+            return Ok(None);
         };
 
-        return Some(ModResult {
+        Ok(Some(ModResult {
             id: response.id.clone(),
             slug: response.slug.clone(),
             name: response.title,
@@ -82,7 +118,7 @@ impl IRepository for ModrinthRepository {
             source: "Modrinth".into(),
             side: get_side(&response.client_side, &response.server_side),
             url: format!("https://modrinth.com/mod/{}", response.slug.clone()),
-        });
+        }))
     }
 
     async fn get_versions(
@@ -90,29 +126,27 @@ impl IRepository for ModrinthRepository {
         project_id: &str,
         game_versions: &[String],
         loaders: &[String],
-    ) -> Vec<VersionResult> {
+    ) -> Result<Vec<VersionResult>, McpmError> {
         let url = format!(
-            "https://api.modrinth.com/v2/project/{}/version?game_versions={}&loaders={}",
+            "{}/project/{}/version?game_versions={}&loaders={}",
+            self.base_url,
             project_id,
             serde_json::to_string(game_versions).unwrap(),
             serde_json::to_string(loaders).unwrap(),
         );
 
-        let res = match self.client.get(&url).send().await {
-            Ok(r) => r,
-            Err(_) => return vec![],
-        };
+        let parsed: Vec<VersionItem> = self
+            .get_json(&url)
+            .await?
+            .ok_or_else(|| McpmError::NotFound(format!("Project '{}'", project_id)))?;
 
-        let parsed: Vec<VersionItem> = match res.json().await {
-            Ok(p) => p,
-            Err(_) => return vec![],
-        };
-
-        parsed
+        Ok(parsed
             .into_iter()
             .map(|v: VersionItem| VersionResult {
                 mod_id: v.project_id,
+                version_id: v.id,
                 version: v.version_number,
+                version_type: v.version_type,
                 minecraft_versions: v.game_versions,
                 url: v
                     .files
@@ -132,8 +166,18 @@ impl IRepository for ModrinthRepository {
                             .and_then(|f| f.hashes.get("sha1").cloned())
                     })
                     .unwrap_or("".to_string()),
+                size: v.files.iter().find(|f| f.primary).map(|f| f.size),
+                dependencies: v
+                    .dependencies
+                    .iter()
+                    .filter(|d| d.dependency_type == "required")
+                    .filter_map(|d| d.project_id.clone())
+                    .collect(),
+                published: DateTime::parse_from_rfc3339(&v.date_published)
+                    .ok()
+                    .map(|d| d.with_timezone(&Utc)),
             })
-            .collect()
+            .collect())
     }
 }
 
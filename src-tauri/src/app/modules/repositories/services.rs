@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::app::modules::repositories::models::VersionResult;
+use crate::app::{modules::repositories::models::VersionResult, McpmError};
 
 use super::interfaces::IRepository;
 use super::models::ModResult;
@@ -9,6 +9,12 @@ pub struct RepositoryService {
     repositories: HashMap<String, Box<dyn IRepository>>,
 }
 
+impl Default for RepositoryService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RepositoryService {
     pub fn new() -> Self {
         Self {
@@ -21,22 +27,25 @@ impl RepositoryService {
         self
     }
 
-    fn get_provider(&self, name: &str) -> Option<&Box<dyn IRepository>> {
-        self.repositories.get(&name.to_lowercase())
+    fn get_provider(&self, name: &str) -> Option<&dyn IRepository> {
+        self.repositories
+            .get(&name.to_lowercase())
+            .map(|p| p.as_ref())
     }
 
-    pub async fn search(&self, query: &str, page: usize) -> Vec<ModResult> {
+    pub async fn search(&self, query: &str, page: usize) -> Result<Vec<ModResult>, McpmError> {
         let mut results = Vec::new();
         for provider in self.repositories.values() {
-            let mut r = provider.search(query, page).await;
+            let mut r = provider.search(query, page).await?;
             results.append(&mut r);
         }
-        results
+        Ok(results)
     }
 
-    /// Find a mod by slug — currently checks only the first registered provider
-    pub async fn find(&self, slug: &str) -> Option<ModResult> {
-        let provider = self.repositories.values().next()?;
+    /// Find a mod by `provider:slug` or `provider:id`, a bare slug or id asks modrinth.
+    /// `Ok(None)` if the provider does not know it.
+    pub async fn find(&self, project_ref: &str) -> Result<Option<ModResult>, McpmError> {
+        let (provider, slug) = self.route(project_ref)?;
         provider.find(slug).await
     }
 
@@ -45,21 +54,23 @@ impl RepositoryService {
         project_id: &str,
         game_versions: &[String],
         loaders: &[String],
-    ) -> Vec<VersionResult> {
-        let (provider_name, clean_id) = match project_id.split_once(':') {
+    ) -> Result<Vec<VersionResult>, McpmError> {
+        let (provider, clean_id) = self.route(project_id)?;
+        provider
+            .get_versions(clean_id, game_versions, loaders)
+            .await
+    }
+
+    /// The provider a `provider:id` reference points at, and the id within it
+    fn route<'a>(&self, project_ref: &'a str) -> Result<(&dyn IRepository, &'a str), McpmError> {
+        let (provider_name, clean_id) = match project_ref.split_once(':') {
             Some((prov, id)) => (prov.to_lowercase(), id),
-            None => ("modrinth".to_string(), project_id),
+            None => ("modrinth".to_string(), project_ref),
         };
 
-        let mut results = Vec::new();
-
-        if let Some(provider) = self.get_provider(&provider_name) {
-            let mut r = provider
-                .get_versions(clean_id, game_versions, loaders)
-                .await;
-            results.append(&mut r);
-        }
-
-        results
+        let provider = self
+            .get_provider(&provider_name)
+            .ok_or_else(|| McpmError::NotFound(format!("Provider '{}'", provider_name)))?;
+        Ok((provider, clean_id))
     }
 }
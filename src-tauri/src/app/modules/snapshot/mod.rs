@@ -0,0 +1,8 @@
+pub mod models;
+
+mod services;
+
+pub use services::SnapshotService;
+
+#[cfg(test)]
+mod snapshot_spec;
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+/// The project state right before an install changed it
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Snapshot {
+    pub id: String,
+    pub created_at: String, // RFC 3339
+    pub note: String,
+    pub manifest: Option<String>, // raw mcpm.json, None if it did not exist
+    pub lock: Option<String>,     // raw mcpm.lock, None if it did not exist
+    pub blobs: Vec<String>,       // cache file names the lock refers to
+}
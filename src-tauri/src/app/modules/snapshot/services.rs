@@ -0,0 +1,160 @@
+use chrono::{DateTime, FixedOffset, Local};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::app::{
+    modules::{lock::models::LockFile, snapshot::models::Snapshot},
+    Config, McpmError,
+};
+
+const SNAPSHOT_DIR: &str = ".mcpm/snapshots";
+
+pub struct SnapshotService {
+    dir: PathBuf,
+}
+
+impl Default for SnapshotService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SnapshotService {
+    /// Snapshots of the project, kept next to its manifest
+    pub fn new() -> Self {
+        Self::at(Config::get().project_dir.join(SNAPSHOT_DIR))
+    }
+
+    pub fn at(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// Record the manifest and lock currently on disk, together with the
+    /// cache blobs the lock refers to.
+    pub fn capture(
+        &self,
+        manifest_path: &Path,
+        lock_path: &Path,
+        cache_dir: &Path,
+        note: &str,
+    ) -> io::Result<Snapshot> {
+        let manifest = fs::read_to_string(manifest_path).ok();
+        let lock = fs::read_to_string(lock_path).ok();
+
+        let mut blobs: Vec<String> = lock
+            .as_deref()
+            .and_then(|l| LockFile::parse(l).ok())
+            .map(|(l, _)| l)
+            .map(|l| {
+                l.mods
+                    .iter()
+                    .map(|(key, entry)| entry.file_name(key))
+                    .filter(|f| cache_dir.join(f).exists())
+                    .collect()
+            })
+            .unwrap_or_default();
+        blobs.sort();
+
+        let now = Local::now();
+        let snapshot = Snapshot {
+            id: self.next_id(&now.format("%Y%m%d-%H%M%S").to_string()),
+            created_at: now.to_rfc3339(),
+            note: note.to_string(),
+            manifest,
+            lock,
+            blobs,
+        };
+
+        fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string_pretty(&snapshot).map_err(io::Error::other)?;
+        fs::write(self.dir.join(format!("{}.json", snapshot.id)), json)?;
+
+        Ok(snapshot)
+    }
+
+    /// All recorded snapshots, newest first. Snapshots taken in the same second
+    /// are told apart by the counter in their id, see [`Self::next_id`].
+    pub fn list(&self) -> Vec<Snapshot> {
+        let mut snapshots: Vec<Snapshot> = fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+                    .filter_map(|e| fs::read_to_string(e.path()).ok())
+                    .filter_map(|s| serde_json::from_str(&s).ok())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        snapshots.sort_by_key(|s| std::cmp::Reverse(order(s)));
+        snapshots
+    }
+
+    pub fn get(&self, id: &str) -> Option<Snapshot> {
+        let content = fs::read_to_string(self.dir.join(format!("{}.json", id))).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Write the snapshot's manifest and lock back to disk.
+    /// Fails without touching anything if a blob is missing from the cache.
+    pub fn restore(
+        &self,
+        snapshot: &Snapshot,
+        manifest_path: &Path,
+        lock_path: &Path,
+        cache_dir: &Path,
+    ) -> Result<(), McpmError> {
+        let missing: Vec<&str> = snapshot
+            .blobs
+            .iter()
+            .filter(|b| !cache_dir.join(b).exists())
+            .map(|b| b.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(McpmError::NotFound(format!(
+                "Cached files for snapshot {} ({})",
+                snapshot.id,
+                missing.join(", ")
+            )));
+        }
+
+        for (path, content) in [
+            (manifest_path, &snapshot.manifest),
+            (lock_path, &snapshot.lock),
+        ] {
+            match content {
+                Some(c) => fs::write(path, c),
+                None if path.exists() => fs::remove_file(path),
+                None => Ok(()),
+            }
+            .map_err(|e| McpmError::io(format!("Failed to restore {}", path.display()), e))?;
+        }
+
+        Ok(())
+    }
+
+    /// `base`, or `base-2`, `base-3`, ... when a snapshot of that second exists
+    fn next_id(&self, base: &str) -> String {
+        let mut id = base.to_string();
+        let mut n = 2;
+        while self.dir.join(format!("{}.json", id)).exists() {
+            id = format!("{}-{}", base, n);
+            n += 1;
+        }
+        id
+    }
+}
+
+/// Creation time, then the same-second counter of the id
+fn order(snapshot: &Snapshot) -> (Option<DateTime<FixedOffset>>, u32) {
+    let created = DateTime::parse_from_rfc3339(&snapshot.created_at).ok();
+    let counter = snapshot
+        .id
+        .splitn(3, '-')
+        .nth(2)
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1);
+    (created, counter)
+}
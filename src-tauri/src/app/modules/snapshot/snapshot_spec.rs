@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use tempfile::tempdir;
+
+    use crate::app::modules::snapshot::{models::Snapshot, SnapshotService};
+
+    // Restoring a snapshot brings back the previous manifest and lock
+    // and refuses to run when a cached jar has disappeared.
+
+    #[test]
+    fn restore_brings_back_previous_manifest_and_lock() {
+        let temp = tempdir().unwrap();
+        let root = temp.path();
+        let manifest = root.join("mcpm.json");
+        let lock = root.join("mcpm.lock");
+        let cache = root.join("cache");
+        fs::create_dir_all(&cache).unwrap();
+
+        fs::write(&manifest, r#"{"mods":{"modrinth:testmod":"1.0.0"}}"#).unwrap();
+        fs::write(&lock, lock_json("1.0.0")).unwrap();
+        fs::write(cache.join("modrinth:testmod-1.0.0.jar"), b"jar-v1").unwrap();
+
+        let service = SnapshotService::at(root.join(".mcpm/snapshots"));
+        let first = service.capture(&manifest, &lock, &cache, "first").unwrap();
+        let second = service.capture(&manifest, &lock, &cache, "second").unwrap();
+        assert_eq!(first.blobs, vec!["modrinth:testmod-1.0.0.jar"]);
+        assert_ne!(first.id, second.id);
+
+        let ids: Vec<_> = service.list().into_iter().map(|s| s.note).collect();
+        assert_eq!(ids, vec!["second", "first"]);
+
+        // Move on to v2, then roll back
+        fs::write(&manifest, r#"{"mods":{"modrinth:testmod":"2.0.0"}}"#).unwrap();
+        fs::write(&lock, lock_json("2.0.0")).unwrap();
+
+        let snapshot = service.get(&first.id).expect("snapshot not found");
+        service
+            .restore(&snapshot, &manifest, &lock, &cache)
+            .unwrap();
+        assert!(fs::read_to_string(&manifest).unwrap().contains("1.0.0"));
+        assert_eq!(fs::read_to_string(&lock).unwrap(), lock_json("1.0.0"));
+
+        // Without the cached jar the restore must not touch anything
+        fs::write(&manifest, "changed").unwrap();
+        fs::remove_file(cache.join("modrinth:testmod-1.0.0.jar")).unwrap();
+        assert!(service
+            .restore(&snapshot, &manifest, &lock, &cache)
+            .is_err());
+        assert_eq!(fs::read_to_string(&manifest).unwrap(), "changed");
+    }
+
+    #[test]
+    fn same_second_snapshots_list_by_their_counter() {
+        let temp = tempdir().unwrap();
+        let dir = temp.path().join(".mcpm/snapshots");
+        fs::create_dir_all(&dir).unwrap();
+        let write = |id: &str, created_at: &str| {
+            let snapshot = Snapshot {
+                id: id.to_string(),
+                created_at: created_at.to_string(),
+                note: String::new(),
+                manifest: None,
+                lock: None,
+                blobs: vec![],
+            };
+            fs::write(
+                dir.join(format!("{}.json", id)),
+                serde_json::to_string(&snapshot).unwrap(),
+            )
+            .unwrap();
+        };
+
+        let second = "2025-01-01T12:00:00+01:00";
+        write("20250101-120000", second);
+        for n in 2..=11 {
+            write(&format!("20250101-120000-{}", n), second);
+        }
+        write("20250101-120001", "2025-01-01T12:00:01+01:00");
+
+        let ids: Vec<_> = SnapshotService::at(dir)
+            .list()
+            .into_iter()
+            .map(|s| s.id)
+            .collect();
+        assert_eq!(ids[0], "20250101-120001");
+        assert_eq!(ids[1], "20250101-120000-11");
+        assert_eq!(ids[2], "20250101-120000-10");
+        assert_eq!(ids[10], "20250101-120000-2");
+        assert_eq!(ids[11], "20250101-120000");
+    }
+
+    fn lock_json(version: &str) -> String {
+        serde_json::json!({
+            "mods": {
+                "modrinth:testmod": {
+                    "id": "testmod",
+                    "version": version,
+                    "minecraft_versions": ["1.21.7"],
+                    "url": "https://example.invalid/testmod.jar",
+                    "hash": "0"
+                }
+            }
+        })
+        .to_string()
+    }
+}
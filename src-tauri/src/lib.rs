@@ -9,9 +9,27 @@ fn greet(name: &str) -> String {
 #[cfg(feature = "gui")]
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    use app::gui::{commands, set_app_handle};
+    use tauri::Manager;
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .invoke_handler(tauri::generate_handler![greet])
+        .setup(|app| {
+            set_app_handle(app.app_handle().clone());
+            Ok(())
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            commands::init,
+            commands::add,
+            commands::remove,
+            commands::install,
+            commands::upgrade,
+            commands::outdated,
+            commands::search,
+            commands::list,
+            commands::answer
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
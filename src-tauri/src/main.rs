@@ -9,23 +9,35 @@ use clap::CommandFactory;
 
 use clap::Parser;
 use mcpm_lib::app::{
-    commands::Cli,
-    modules::io::{init_io, traits::IOConfig},
+    commands::{output::OutputFormat, Cli},
+    detect_interactive,
+    modules::io::{
+        init_io,
+        traits::{IOConfig, LogFormat},
+        use_io,
+    },
     set_interactive, Config,
 };
 
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
-    Config::init(&cli);
 
+    // Text logs would mix with the result document in JSON mode
+    let silence = cli.output == OutputFormat::Json && cli.log_format == LogFormat::Text;
     let io_cfg = IOConfig {
-        verbose: cli.verbose,
-        quiet: cli.quiet,
+        verbose: cli.verbose && !silence,
+        quiet: cli.quiet || silence,
+        format: cli.log_format,
+        assume_yes: cli.yes,
     };
+    set_interactive(detect_interactive(cli.non_interactive || cli.yes));
     init_io(io_cfg).await;
 
-    unsafe { set_interactive(io_cfg.verbose || io_cfg.quiet) }
+    if let Err(e) = Config::init(&cli) {
+        use_io().error("Failed to load configuration", Some(&e));
+        std::process::exit(e.exit_code());
+    }
 
     #[cfg(feature = "gui")]
     {
@@ -34,7 +46,10 @@ async fn main() {
     #[cfg(not(feature = "gui"))]
     {
         if let Some(cmd) = cli.command {
-            cmd.run().await;
+            let code = cmd.run().await;
+            if code != 0 {
+                std::process::exit(code);
+            }
         } else {
             let mut cmd = Cli::command();
             println!("mcpm {}", cmd.get_version().unwrap_or("unknown"));